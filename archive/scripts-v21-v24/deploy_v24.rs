@@ -0,0 +1,40 @@
+//! Deploy StakeVue V24 contract (upgradable package)
+//!
+//! V24 Changes:
+//! - Installed as an upgradable package: future versions are installed
+//!   in place with upgrade_v24, keeping the package hash and all balances
+//! - Stored contract_version (see get_contract_version)
+//!
+//! Run with: cargo run --bin deploy_v24 --features livenet
+
+use odra::host::{Deployer, HostRef, InstallConfig};
+use odra::prelude::*;
+use stakevue_contract::StakeVue;
+
+fn main() {
+    let env = odra_casper_livenet_env::env();
+
+    println!("=== Deploying StakeVue V24 ===");
+    println!("V24: upgradable package, state survives future upgrades");
+    println!();
+
+    let owner = env.caller();
+    println!("Owner: {:?}", owner);
+
+    env.set_gas(600_000_000_000u64); // 600 CSPR
+
+    let contract = StakeVue::deploy_with_cfg(
+        &env,
+        stakevue_contract::StakeVueInitArgs { owner },
+        InstallConfig::upgradable::<StakeVue>(),
+    );
+
+    println!();
+    println!("SUCCESS! StakeVue V24 deployed!");
+    println!("Contract address: {:?}", contract.address());
+    println!("Contract version: {}", contract.get_contract_version());
+    println!();
+    println!("Next steps:");
+    println!("1. Add validators: cargo run --bin stakevue --features livenet -- validators add");
+    println!("2. Future releases: cargo run --bin stakevue --features livenet -- upgrade");
+}
@@ -0,0 +1,50 @@
+//! Upgrade an existing StakeVue package in place
+//!
+//! Installs the current contract code into the package deployed by
+//! deploy_v24 and runs the versioned migrations. The package hash,
+//! stCSPR balances and withdrawal requests are preserved.
+//!
+//! Note: V22 and older were installed as locked packages and cannot be
+//! upgraded, only packages deployed with deploy_v24 or later.
+//!
+//! Run with: cargo run --bin upgrade_v24 --features livenet -- <package hash>
+//! The package hash (hash-...) can also be given in STAKEVUE_CONTRACT.
+
+use std::str::FromStr;
+use odra::host::{HostRef, HostRefLoader, NoArgs};
+use odra::prelude::*;
+use stakevue_contract::StakeVue;
+
+const CONTRACT_VAR: &str = "STAKEVUE_CONTRACT";
+
+fn main() {
+    let contract_hash = match std::env::args().nth(1).or_else(|| std::env::var(CONTRACT_VAR).ok()) {
+        Some(hash) => hash,
+        None => {
+            eprintln!("Usage: upgrade_v24 <package hash> (or set {})", CONTRACT_VAR);
+            std::process::exit(2);
+        }
+    };
+    let address = Address::from_str(&contract_hash).expect("Invalid contract hash");
+
+    let env = odra_casper_livenet_env::env();
+
+    println!("=== Upgrading StakeVue ===");
+    println!("Contract: {}", contract_hash);
+
+    let current = StakeVue::load(&env, address);
+
+    println!("Caller (must be owner): {:?}", env.caller());
+    println!("Current version: {}", current.get_contract_version());
+    println!("Total pool: {} motes", current.get_total_pool());
+
+    env.set_gas(600_000_000_000u64); // 600 CSPR
+
+    let upgraded = StakeVue::try_upgrade(&env, address, NoArgs).expect("Upgrade failed");
+
+    println!();
+    println!("SUCCESS! StakeVue upgraded in place.");
+    println!("Contract address: {:?}", upgraded.address());
+    println!("New version: {}", upgraded.get_contract_version());
+    println!("Total pool: {} motes", upgraded.get_total_pool());
+}
@@ -1,10 +1,93 @@
+//! Generic Odra CLI for StakeVue entry points.
+//!
+//! The network profile is selected with --profile (or STAKEVUE_PROFILE) and
+//! read from --profiles-file, which defaults to resources/profiles.toml
+//! under the crate root whatever the working directory.
+//!
+//! Usage: cargo run --bin stakevue_cli --features livenet -- --profile testnet <odra-cli args>
+//!
+//! OdraCli parses the process arguments itself, so when either flag is
+//! given the binary runs itself again without them and passes them on in
+//! STAKEVUE_PROFILE and STAKEVUE_PROFILES_FILE.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::Command;
 use odra_cli::OdraCli;
+use stakevue_contract::profile::{default_profiles_path, Profile};
 use stakevue_contract::StakeVue;
 
+const PROFILE_VAR: &str = "STAKEVUE_PROFILE";
+const PROFILES_FILE_VAR: &str = "STAKEVUE_PROFILES_FILE";
+
 pub fn main() {
+    let mut args: Vec<OsString> = std::env::args_os().skip(1).collect();
+    let profile = take_flag(&mut args, "--profile");
+    let profiles_file = take_flag(&mut args, "--profiles-file");
+    if profile.is_some() || profiles_file.is_some() {
+        std::process::exit(rerun(args, profile, profiles_file));
+    }
+
+    if let Ok(name) = std::env::var(PROFILE_VAR) {
+        let path = std::env::var_os(PROFILES_FILE_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(default_profiles_path);
+        match Profile::load(&path, &name) {
+            Ok(profile) => profile.apply_env(),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     OdraCli::new()
         .about("StakeVue CLI")
         .contract::<StakeVue>()
         .build()
         .run();
 }
+
+/// Removes `--flag value` or `--flag=value` from `args`, returning the value.
+fn take_flag(args: &mut Vec<OsString>, flag: &str) -> Option<OsString> {
+    let prefix = format!("{}=", flag);
+    let index = args.iter().position(|arg| {
+        arg.to_str().is_some_and(|arg| arg == flag || arg.starts_with(&prefix))
+    })?;
+    let arg = args.remove(index);
+    let arg = arg.to_str().unwrap_or_default();
+    match arg.strip_prefix(&prefix) {
+        Some(value) => Some(value.into()),
+        None if index < args.len() => Some(args.remove(index)),
+        None => {
+            eprintln!("Error: {} needs a value", flag);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Runs this binary again with `args` and the profile in the environment.
+fn rerun(args: Vec<OsString>, profile: Option<OsString>, profiles_file: Option<OsString>) -> i32 {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    let mut command = Command::new(exe);
+    command.args(args);
+    if let Some(profile) = profile {
+        command.env(PROFILE_VAR, profile);
+    }
+    if let Some(profiles_file) = profiles_file {
+        command.env(PROFILES_FILE_VAR, profiles_file);
+    }
+    match command.status() {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
@@ -1,13 +0,0 @@
-//! DEPRECATED: V16 is replaced by V17 (Multi-Validator)
-//! Use: cargo run --bin add_validators_v17 --features livenet
-//!
-//! This script is kept for reference only.
-
-fn main() {
-    println!("=== DEPRECATED ===");
-    println!("V16 single-validator support has been replaced by V17 multi-validator.");
-    println!();
-    println!("Use instead:");
-    println!("  cargo run --bin deploy_v17 --features livenet");
-    println!("  cargo run --bin add_validators_v17 --features livenet");
-}
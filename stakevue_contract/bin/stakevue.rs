@@ -0,0 +1,352 @@
+//! StakeVue operator CLI
+//!
+//! One binary for every operator task, replacing the per-version
+//! deploy_vNN / add_validators_vNN / admin_*_vNN / test_*_vNN scripts.
+//!
+//! Network, contract, validators and gas come from a profile in
+//! resources/profiles.toml (--profile or STAKEVUE_PROFILE). --contract or
+//! STAKEVUE_CONTRACT override the profile's contract, --gas its gas limits.
+//! Amounts are decimal CSPR (or stCSPR for unstake), e.g. 1000 or 2.5.
+//!
+//! Usage: cargo run --bin stakevue --features livenet -- --profile testnet <command>
+//!
+//! Examples:
+//!   stakevue deploy --upgradable
+//!   stakevue validators add                  (profile's validator set)
+//!   stakevue validators add 0106ca7c... 01a62e86...
+//!   stakevue validators list
+//!   stakevue stake 1000 --validator 0106ca7c...
+//!   stakevue delegate 0106ca7c... 500
+//!   stakevue status
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use clap::{Parser, Subcommand};
+use odra::casper_types::{AsymmetricType, PublicKey};
+use odra::host::{Deployer, HostEnv, HostRef, HostRefLoader, InstallConfig, NoArgs};
+use odra::prelude::*;
+use stakevue_contract::amounts::{format_units, Cspr, Motes};
+use stakevue_contract::profile::{default_profiles_path, GasSettings, Profile};
+use stakevue_contract::submit::{submit, RetryPolicy};
+use stakevue_contract::{StakeVue, StakeVueHostRef, StakeVueInitArgs};
+
+#[derive(Parser)]
+#[command(name = "stakevue", about = "StakeVue operator CLI")]
+struct Cli {
+    /// Network profile from the profiles file
+    #[arg(long, env = "STAKEVUE_PROFILE", global = true)]
+    profile: Option<String>,
+
+    /// Profiles file (default resources/profiles.toml under the crate root)
+    #[arg(long, global = true)]
+    profiles_file: Option<PathBuf>,
+
+    /// Contract package hash (hash-...), overrides the profile
+    #[arg(long, global = true)]
+    contract: Option<String>,
+
+    /// Gas limit in CSPR (defaults to the profile's limit for the command)
+    #[arg(long, global = true)]
+    gas: Option<u64>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Deploy a new StakeVue contract (caller becomes owner)
+    Deploy {
+        /// Install as an upgradable package (required for `upgrade`)
+        #[arg(long)]
+        upgradable: bool,
+    },
+    /// Upgrade the contract in place and run migrations
+    Upgrade,
+    /// Manage approved validators
+    Validators {
+        #[command(subcommand)]
+        command: ValidatorsCommand,
+    },
+    /// Stake CSPR and receive stCSPR
+    Stake {
+        /// Amount in CSPR
+        amount: Cspr,
+        /// Validator public key (hex)
+        #[arg(long)]
+        validator: String,
+    },
+    /// Request unstake (burns stCSPR, creates a withdrawal request)
+    Unstake {
+        /// Amount in stCSPR
+        amount: Cspr,
+    },
+    /// Claim a ready withdrawal request
+    Claim {
+        request_id: u64,
+        /// Process the claim for its staker (owner only)
+        #[arg(long)]
+        for_staker: bool,
+    },
+    /// Delegate pool liquidity to a validator (owner only)
+    Delegate {
+        validator: String,
+        /// Amount in CSPR
+        amount: Cspr,
+    },
+    /// Undelegate from a validator (owner only)
+    Undelegate {
+        validator: String,
+        /// Amount in CSPR
+        amount: Cspr,
+    },
+    /// Add unbonded CSPR back to pool liquidity (owner only)
+    AddLiquidity {
+        /// Amount in CSPR
+        amount: Cspr,
+    },
+    /// Add rewards to the pool, raising the exchange rate (owner only)
+    Harvest {
+        /// Amount in CSPR
+        amount: Cspr,
+    },
+    /// Show pool, validator and caller status
+    Status,
+}
+
+#[derive(Subcommand)]
+enum ValidatorsCommand {
+    /// Add validators (skips already active ones)
+    Add {
+        /// Validator public keys (hex), defaults to the profile's set
+        keys: Vec<String>,
+    },
+    /// Queue validator removal in the timelock
+    Remove {
+        key: String,
+        /// Execution time in ms (defaults to now + timelock delay)
+        #[arg(long)]
+        eta: Option<u64>,
+    },
+    /// List validators with metadata and delegation
+    List,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    // The profile must be applied before the env is created
+    let profiles_file = cli.profiles_file.clone().unwrap_or_else(default_profiles_path);
+    let profile = match &cli.profile {
+        Some(name) => match Profile::load(&profiles_file, name) {
+            Ok(profile) => {
+                profile.apply_env();
+                Some(profile)
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let env = odra_casper_livenet_env::env();
+
+    if let Err(e) = run(&env, cli, profile.as_ref()) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(env: &HostEnv, cli: Cli, profile: Option<&Profile>) -> Result<(), String> {
+    let limits = profile.map(|p| p.gas.clone()).unwrap_or_default();
+    let gas = |default: u64| GasSettings::motes(cli.gas.unwrap_or(default));
+
+    if let Command::Deploy { upgradable } = cli.command {
+        let owner = env.caller();
+        println!("Deploying StakeVue (owner {:?}, upgradable: {})...", owner, upgradable);
+        env.set_gas(gas(limits.deploy));
+        let init_args = StakeVueInitArgs { owner };
+        let contract = if upgradable {
+            StakeVue::deploy_with_cfg(env, init_args, InstallConfig::upgradable::<StakeVue>())
+        } else {
+            StakeVue::deploy(env, init_args)
+        };
+        println!("Contract address: {:?}", contract.address());
+        println!("Contract version: {}", contract.get_contract_version());
+        return Ok(());
+    }
+
+    let address = contract_address(cli.contract, profile)?;
+    let mut contract = StakeVue::load(env, address);
+
+    match cli.command {
+        Command::Deploy { .. } => unreachable!(),
+        Command::Upgrade => {
+            println!("Current version: {}", contract.get_contract_version());
+            env.set_gas(gas(limits.deploy));
+            let upgraded = StakeVue::try_upgrade(env, address, NoArgs)
+                .map_err(|e| format!("Upgrade failed: {:?}", e))?;
+            println!("Upgraded to version {}", upgraded.get_contract_version());
+        }
+        Command::Validators { command } => match command {
+            ValidatorsCommand::Add { keys } => {
+                let keys = if keys.is_empty() {
+                    let profile = profile.ok_or("No validator keys given and no --profile selected")?;
+                    profile.validators.iter().map(|v| v.public_key.clone()).collect()
+                } else {
+                    keys
+                };
+                // Confirms each addition and retries across event stream failures
+                let reader = StakeVue::load(env, address);
+                let policy = RetryPolicy::default();
+                for (i, key) in keys.iter().enumerate() {
+                    let validator = parse_validator(key)?;
+                    if contract.is_validator_active(validator.clone()) {
+                        println!("[{}/{}] Already active: {}", i + 1, keys.len(), short(key));
+                        continue;
+                    }
+                    println!("[{}/{}] Adding: {}", i + 1, keys.len(), short(key));
+                    submit(&policy, || {
+                        env.set_gas(gas(limits.admin));
+                        contract.try_add_validator(validator.clone())
+                    }, || reader.is_validator_active(validator.clone()))
+                        .map_err(|e| format!("Adding {} {}", short(key), e))?;
+                }
+                println!("Total validators: {}", contract.get_validator_count());
+            }
+            ValidatorsCommand::Remove { key, eta } => {
+                let validator = parse_validator(&key)?;
+                let eta = eta.unwrap_or(env.block_time() + contract.get_timelock_delay());
+                env.set_gas(gas(limits.admin));
+                let operation_id = contract.queue_remove_validator(validator, eta);
+                println!("Queued removal as operation #{} (executable from {})", operation_id, eta);
+            }
+            ValidatorsCommand::List => print_validators(&contract, profile),
+        },
+        Command::Stake { amount, validator } => {
+            let validator = parse_validator(&validator)?;
+            env.set_gas(gas(limits.stake));
+            contract.with_tokens(amount.motes().value()).stake(validator);
+            println!("Staked {} CSPR, stCSPR balance: {}", amount, Motes::from_u256(contract.get_stcspr_balance(env.caller())).cspr());
+        }
+        Command::Unstake { amount } => {
+            env.set_gas(gas(limits.unstake));
+            let request_id = contract.request_unstake(amount.motes().value());
+            println!("Withdrawal request #{} created", request_id);
+        }
+        Command::Claim { request_id, for_staker } => {
+            env.set_gas(gas(limits.admin));
+            if for_staker {
+                contract.admin_process_claim(request_id);
+            } else {
+                contract.claim(request_id);
+            }
+            println!("Claimed request #{}", request_id);
+        }
+        Command::Delegate { validator, amount } => {
+            let validator = parse_validator(&validator)?;
+            env.set_gas(gas(limits.delegate));
+            contract.admin_delegate(validator.clone(), amount.motes().value());
+            println!("Delegated {} CSPR, validator total: {:#}", amount, Motes(contract.get_delegated_to_validator(validator)).cspr());
+        }
+        Command::Undelegate { validator, amount } => {
+            let validator = parse_validator(&validator)?;
+            env.set_gas(gas(limits.undelegate));
+            contract.admin_undelegate(validator, amount.motes().value());
+            println!("Undelegated {} CSPR (unbonding ~7 eras, then add-liquidity)", amount);
+        }
+        Command::AddLiquidity { amount } => {
+            env.set_gas(gas(limits.admin));
+            contract.with_tokens(amount.motes().value()).admin_add_liquidity();
+            println!("Added {} CSPR, available liquidity: {:#}", amount, Motes(contract.get_available_liquidity()).cspr());
+        }
+        Command::Harvest { amount } => {
+            env.set_gas(gas(limits.admin));
+            contract.with_tokens(amount.motes().value()).harvest_rewards();
+            println!("Harvested {} CSPR, exchange rate: {}", amount, format_units(contract.get_exchange_rate()));
+        }
+        Command::Status => print_status(env, &contract, profile),
+    }
+
+    Ok(())
+}
+
+fn print_status(env: &HostEnv, contract: &StakeVueHostRef, profile: Option<&Profile>) {
+    println!("=== StakeVue Status ===");
+    if let Some(profile) = profile {
+        println!("Profile: {} ({})", profile.name, profile.chain_name);
+    }
+    println!("Contract version: {}", contract.get_contract_version());
+    println!("Owner: {:?}", contract.get_owner());
+
+    println!("\n--- Pool ---");
+    println!("Total pool: {:#}", Motes(contract.get_total_pool()).cspr());
+    println!("Available liquidity: {:#}", Motes(contract.get_available_liquidity()).cspr());
+    println!("Pending withdrawals: {:#}", Motes(contract.get_pending_withdrawals()).cspr());
+    println!("Pending undelegations: {:#}", Motes(contract.get_pending_undelegations()).cspr());
+    println!("stCSPR supply: {} stCSPR", Motes::from_u256(contract.token_total_supply()).cspr());
+    println!("Exchange rate: {} CSPR per stCSPR", format_units(contract.get_exchange_rate()));
+    println!("Emergency mode: {}", contract.is_emergency_mode());
+
+    println!("\n--- Validators ---");
+    print_validators(contract, profile);
+
+    let caller = env.caller();
+    println!("\n--- Caller {:?} ---", caller);
+    println!("stCSPR balance: {} stCSPR", Motes::from_u256(contract.get_stcspr_balance(caller)).cspr());
+    println!("CSPR value: {:#}", Motes(contract.get_cspr_value(caller)).cspr());
+    for index in 0..contract.get_user_request_count(caller) {
+        if let Some(id) = contract.get_user_request_id(caller, index) {
+            let state = if contract.is_withdrawal_claimed(id) {
+                "claimed"
+            } else if contract.is_withdrawal_ready(id) {
+                "READY TO CLAIM"
+            } else {
+                "waiting"
+            };
+            println!("  Request #{}: {:#} - {}", id, Motes(contract.get_withdrawal_amount(id)).cspr(), state);
+        }
+    }
+}
+
+fn print_validators(contract: &StakeVueHostRef, profile: Option<&Profile>) {
+    let count = contract.get_validator_count();
+    println!("Validators: {}", count);
+    for i in 0..count {
+        if let Some(v) = contract.get_validator(i) {
+            let hex = v.to_hex();
+            let name = contract.get_validator_info(v.clone())
+                .map(|info| info.name)
+                .filter(|name| !name.is_empty())
+                .or_else(|| profile.map(|p| p.validator_name(&hex)))
+                .unwrap_or_else(|| String::from("-"));
+            println!("  [{}] {} {} - Active: {}, Delegated: {:#}",
+                i,
+                short(&hex),
+                name,
+                contract.is_validator_active(v.clone()),
+                Motes(contract.get_delegated_to_validator(v)).cspr());
+        }
+    }
+}
+
+/// --contract, then STAKEVUE_CONTRACT (may be set in .env), then the profile.
+fn contract_address(contract: Option<String>, profile: Option<&Profile>) -> Result<Address, String> {
+    match contract.or_else(|| std::env::var("STAKEVUE_CONTRACT").ok()) {
+        Some(hash) => Address::from_str(&hash)
+            .map_err(|e| format!("Invalid contract hash {}: {:?}", hash, e)),
+        None => profile
+            .ok_or_else(|| String::from("No contract: pass --profile, --contract or set STAKEVUE_CONTRACT"))?
+            .contract_address()
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn parse_validator(key: &str) -> Result<PublicKey, String> {
+    PublicKey::from_hex(key).map_err(|e| format!("Invalid validator key {}: {:?}", key, e))
+}
+
+fn short(key: &str) -> &str {
+    &key[..key.len().min(16)]
+}
@@ -1,72 +1,306 @@
 //! StakeVue V20 Full Automation Bot
 //!
 //! Handles ALL admin tasks automatically:
-//! 1. Auto-delegate when pool >= 500 CSPR
-//! 2. Auto-undelegate when users request unstake
+//! 1. Auto-delegate when pool >= 500 CSPR, within validator limits
+//! 2. Auto-undelegate when users request unstake, split across validators
 //! 3. Auto-add-liquidity after unbonding period
 //! 4. Auto-claim for users (sends CSPR directly to them)
 //!
+//! Each confirmed undelegation is tracked in the state until its unbonded
+//! CSPR shows up in the contract purse or the owner's account (see
+//! src/unbonding.rs); the bot then adds it to pool liquidity and pays ready
+//! claims from it before delegating what is left.
+//!
 //! User only needs to: STAKE and UNSTAKE
 //! Everything else is automatic!
 //!
-//! Usage: cargo run --bin stakevue_bot --features livenet
+//! Contract, validators (round-robin delegation targets) and gas limits
+//! come from the selected network profile in resources/profiles.toml.
+//!
+//! Usage: cargo run --bin stakevue_bot --features livenet -- --profile testnet
+//!
+//! State (round-robin position, totals) and a journal of submitted deploys
+//! with their hashes are kept in --state-dir (default
+//! .stakevue_bot/<profile>/). After a restart, deploys left unfinished by
+//! the previous run are settled by their execution result, or by contract
+//! state when the node has no record of them, before any new action is
+//! taken. Deploys that executed
+//! without their effect showing up are checked again every cycle. A cycle
+//! whose node queries fail counts as an error and the next one retries it.
+//!
+//! Alert rules from the profile's [alerts] table are evaluated after every
+//! cycle and sent to its webhook_url when they fire or recover.
+//!
+//! Monitoring: --metrics-addr 0.0.0.0:9898 serves Prometheus /metrics and
+//! /healthz; --log-format json replaces the console output with one JSON
+//! object per line.
+//!
+//! Review the next cycle without submitting anything:
+//!   stakevue_bot --profile testnet --dry-run [--plan-json plan.json]
 
+use clap::{Parser, ValueEnum};
 use odra::casper_types::{U512, PublicKey, AsymmetricType};
-use odra::host::HostRefLoader;
+use odra::host::{HostEnv, HostRef, HostRefLoader};
 use odra::prelude::*;
-use stakevue_contract::StakeVue;
-use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use stakevue_contract::alerts::{AlertEngine, AlertStatus, CycleSnapshot, Webhook};
+use stakevue_contract::amounts::Motes;
+use stakevue_contract::journal::{EntryStatus, Journal, JournalEntry};
+use stakevue_contract::node::{DeployOutcome, FoundDeploy, NodeClient};
+use stakevue_contract::planner::{plan_undelegations, ValidatorPosition};
+use stakevue_contract::profile::{default_profiles_path, GasSettings, Profile};
+use stakevue_contract::submit::{guarded, submit, RetryPolicy, SubmitError};
+use stakevue_contract::telemetry::{self, log_json, BotMetrics, SharedMetrics, ValidatorGauge};
+use stakevue_contract::unbonding::{Balances, Source, Unbonding, UnbondingTracker};
+use stakevue_contract::{StakeVue, StakeVueHostRef};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{thread, time::Duration};
 
 // ============================================================================
 // CONFIGURATION
 // ============================================================================
 
-const CONTRACT_HASH: &str = "hash-2d74e6397ffa1e7fcb63a18e0b4f60f5b2d14242273fce0f30efc0e95ce8e937";
-
-// Validators to delegate to (round-robin distribution)
-const VALIDATORS: &[&str] = &[
-    "0106ca7c39cd272dbf21a86eeb3b36b7c26e2e9b94af64292419f7862936bca2ca", // Make
-    "01a62e8605be4c984ee547ac3da0cf3541561a92c5bb5de699aa4ec095b471bc81", // Arcadia
-];
-
 // Timing
 const CHECK_INTERVAL_SECS: u64 = 60;      // Check every 1 minute
-const MIN_DELEGATION: u64 = 500_000_000_000; // 500 CSPR
+const MIN_DELEGATION_CSPR: u64 = 500;
+const UNCONFIRMED_GIVE_UP_SECS: u64 = 60 * 60;  // Re-check unconfirmed deploys for 1 hour
+
+// First contract version with get_purse_surplus, get_validator_headroom,
+// get_validator_info and admin_credit_purse_liquidity
+const MIN_CONTRACT_VERSION: u32 = 24;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human readable console output
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+static JSON_LOGS: AtomicBool = AtomicBool::new(false);
+
+/// Console output, silenced when logging JSON.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !JSON_LOGS.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Error output: stderr, or an `error` event when logging JSON.
+macro_rules! esay {
+    ($($arg:tt)*) => {
+        if JSON_LOGS.load(Ordering::Relaxed) {
+            event("error", "error", serde_json::json!({ "message": format!($($arg)*) }));
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Structured event, only emitted when logging JSON.
+fn event(level: &str, name: &str, fields: serde_json::Value) {
+    if JSON_LOGS.load(Ordering::Relaxed) {
+        log_json(level, name, fields);
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "stakevue_bot", about = "StakeVue automation bot")]
+struct Args {
+    /// Network profile from the profiles file
+    #[arg(long, env = "STAKEVUE_PROFILE", default_value = "testnet")]
+    profile: String,
+
+    /// Profiles file (default resources/profiles.toml under the crate root)
+    #[arg(long)]
+    profiles_file: Option<PathBuf>,
 
-// Gas limits
-const GAS_DELEGATE: u64 = 50_000_000_000;    // 50 CSPR
-const GAS_UNDELEGATE: u64 = 50_000_000_000;  // 50 CSPR
-const GAS_ADD_LIQUIDITY: u64 = 5_000_000_000; // 5 CSPR
-const GAS_CLAIM: u64 = 5_000_000_000;        // 5 CSPR
+    /// Directory for state.json and journal.jsonl (default .stakevue_bot/<profile>)
+    #[arg(long)]
+    state_dir: Option<PathBuf>,
+
+    /// Serve Prometheus /metrics and /healthz on this address
+    #[arg(long)]
+    metrics_addr: Option<String>,
+
+    /// /healthz fails when no cycle succeeded for this many seconds
+    #[arg(long, default_value_t = 600)]
+    health_max_age_secs: u64,
+
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Print the action plan for one cycle without submitting deploys
+    #[arg(long)]
+    dry_run: bool,
+
+    /// With --dry-run, also write the plan as JSON to this file
+    #[arg(long, requires = "dry_run")]
+    plan_json: Option<PathBuf>,
+}
 
 // ============================================================================
 // BOT STATE
 // ============================================================================
 
-struct BotState {
+/// Survives restarts via state.json.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SavedState {
     validator_index: usize,
-    total_delegated: u64,
-    total_undelegated: u64,
-    total_claimed: u64,
+    total_delegated: Motes,
+    total_undelegated: Motes,
+    total_claimed: Motes,
+    total_liquidity_added: Motes,
     cycles: u64,
+    /// Last journal entry whose outcome is reflected in this state.
+    journal_applied: u64,
+    /// Undelegations whose CSPR is not back in pool liquidity yet.
+    unbonding: UnbondingTracker,
+}
+
+struct BotState {
+    profile: Profile,
+    contract: Address,
+    validators: Vec<PublicKey>,
+    saved: SavedState,
+    state_path: PathBuf,
+    journal: Journal,
+    /// Finds the deploys behind journal entries.
+    node: NodeClient,
+    metrics: SharedMetrics,
+    alerts: AlertEngine,
+    webhook: Option<Webhook>,
+    /// Filled by the running cycle for alert evaluation.
+    cycle_snapshot: CycleSnapshot,
+    /// Whether the previous run's journal has been reconciled.
+    recovered: bool,
 }
 
 impl BotState {
-    fn new() -> Self {
-        Self {
-            validator_index: 0,
-            total_delegated: 0,
-            total_undelegated: 0,
-            total_claimed: 0,
-            cycles: 0,
+    fn new(profile: Profile, state_dir: &Path) -> Result<Self, String> {
+        let contract = profile.contract_address().map_err(|e| e.to_string())?;
+        let validators = profile.validator_keys().map_err(|e| e.to_string())?;
+        if validators.is_empty() {
+            return Err(format!("Profile '{}' has no validators", profile.name));
+        }
+
+        let state_path = state_dir.join("state.json");
+        let mut saved: SavedState = match std::fs::read_to_string(&state_path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Invalid {}: {}", state_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => SavedState::default(),
+            Err(e) => return Err(format!("Cannot read {}: {}", state_path.display(), e)),
+        };
+        // The validator set may have shrunk since the last run
+        saved.validator_index %= validators.len();
+
+        let journal = Journal::open(&state_dir.join("journal.jsonl"))
+            .map_err(|e| format!("Cannot open journal: {}", e))?;
+
+        let alerts = AlertEngine::new(profile.alerts.clone());
+        let webhook = alerts.webhook_url().map(|url| Webhook::new(url, &profile.name));
+
+        Ok(Self {
+            alerts,
+            webhook,
+            cycle_snapshot: CycleSnapshot::default(),
+            recovered: false,
+            node: NodeClient::new(&profile.node_address),
+            profile,
+            contract,
+            validators,
+            saved,
+            state_path,
+            journal,
+            metrics: BotMetrics::shared(),
+        })
+    }
+
+    /// Writes state.json atomically (temp file + rename).
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.saved).map_err(|e| e.to_string())?;
+        let tmp = self.state_path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, &self.state_path))
+            .map_err(|e| format!("Cannot write {}: {}", self.state_path.display(), e))
+    }
+
+    /// Validators in round-robin order, starting with the next one.
+    fn round_robin(&self) -> impl Iterator<Item = &PublicKey> + '_ {
+        let count = self.validators.len();
+        (0..count).map(move |i| &self.validators[(self.saved.validator_index + i) % count])
+    }
+
+    /// Moves the round-robin position past `validator`.
+    fn advance_past(&mut self, validator: &PublicKey) {
+        if let Some(index) = self.validators.iter().position(|v| v == validator) {
+            self.saved.validator_index = (index + 1) % self.validators.len();
+        }
+    }
+
+    /// Folds a succeeded action into the totals, round-robin position and
+    /// unbonding tracker.
+    fn apply(&mut self, entry: &JournalEntry, action: &Action) {
+        match action {
+            Action::Delegate { validator, amount } => {
+                self.advance_past(validator);
+                self.saved.total_delegated = self.saved.total_delegated.saturating_add(Motes(*amount));
+            }
+            Action::Undelegate { validator, amount } => {
+                self.saved.total_undelegated = self.saved.total_undelegated.saturating_add(Motes(*amount));
+                let before = Balances {
+                    purse_surplus: record_motes(&entry.action, "purse_surplus_before_motes"),
+                    owner: record_motes(&entry.action, "owner_balance_before_motes"),
+                };
+                self.saved.unbonding.track(Unbonding {
+                    journal_id: entry.id,
+                    validator: validator.to_hex(),
+                    amount: Motes(*amount),
+                    undelegated_at: entry.timestamp,
+                }, before);
+            }
+            Action::AddLiquidity { undelegation, source, amount } => {
+                self.saved.total_liquidity_added = self.saved.total_liquidity_added.saturating_add(Motes(*amount));
+                self.saved.unbonding.finalize(*undelegation, *source);
+            }
+            Action::Claim { amount, .. } => {
+                self.saved.total_claimed = self.saved.total_claimed.saturating_add(Motes(*amount));
+            }
+        }
+    }
+
+    fn update_metrics(&self, pool: &PoolSnapshot) {
+        let mut m = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        m.total_pool = pool.total_pool;
+        m.available_liquidity = pool.available_liquidity;
+        m.pending_undelegations = pool.pending_undelegations;
+        m.pending_withdrawals = pool.pending_withdrawals;
+        m.exchange_rate = pool.exchange_rate;
+        m.unbonding = self.saved.unbonding.outstanding().value();
+        for (validator, delegated) in &pool.delegated {
+            let key = validator.to_hex();
+            let name = self.profile.validator_name(&key);
+            m.validators.insert(key, ValidatorGauge { name, delegated: *delegated });
+        }
+    }
+
+    /// Marks a journal entry as reflected in the saved state.
+    fn settle(&mut self, entry: &JournalEntry) -> Result<(), String> {
+        if entry.status == EntryStatus::Succeeded {
+            if let Some(action) = Action::from_json(&entry.action) {
+                self.apply(entry, &action);
+            }
         }
+        self.saved.journal_applied = self.saved.journal_applied.max(entry.id);
+        self.save()
     }
 
-    fn next_validator(&mut self) -> &'static str {
-        let validator = VALIDATORS[self.validator_index];
-        self.validator_index = (self.validator_index + 1) % VALIDATORS.len();
-        validator
+    fn gas(&self) -> &GasSettings {
+        &self.profile.gas
     }
 }
 
@@ -75,151 +309,758 @@ impl BotState {
 // ============================================================================
 
 fn main() {
-    println!("╔══════════════════════════════════════════════════════════════╗");
-    println!("║       StakeVue V20 - Full Automation Bot                     ║");
-    println!("╠══════════════════════════════════════════════════════════════╣");
-    println!("║  User does:                                                  ║");
-    println!("║    • Stake    → Deposit CSPR, get stCSPR                     ║");
-    println!("║    • Unstake  → Request withdrawal                           ║");
-    println!("║    • Wait     → CSPR arrives automatically!                  ║");
-    println!("╠══════════════════════════════════════════════════════════════╣");
-    println!("║  Bot handles:                                                ║");
-    println!("║    ✓ Auto-delegate to validators                             ║");
-    println!("║    ✓ Auto-undelegate on unstake requests                     ║");
-    println!("║    ✓ Auto-add-liquidity after unbonding                      ║");
-    println!("║    ✓ Auto-claim and send CSPR to users                       ║");
-    println!("╚══════════════════════════════════════════════════════════════╝");
-    println!();
-    println!("Contract: {}", CONTRACT_HASH);
-    println!("Validators: {}", VALIDATORS.len());
-    println!("Check interval: {} seconds", CHECK_INTERVAL_SECS);
-    println!();
-    println!("Press Ctrl+C to stop");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
-
-    let mut state = BotState::new();
+    let args = Args::parse();
+    JSON_LOGS.store(args.log_format == LogFormat::Json, Ordering::Relaxed);
+    let state_dir = args.state_dir.clone()
+        .unwrap_or_else(|| Path::new(".stakevue_bot").join(&args.profile));
+    let profiles_file = args.profiles_file.clone().unwrap_or_else(default_profiles_path);
+    let state = Profile::load(&profiles_file, &args.profile)
+        .map_err(|e| e.to_string())
+        .and_then(|profile| BotState::new(profile, &state_dir));
+    let mut state = match state {
+        Ok(state) => state,
+        Err(e) => {
+            esay!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    state.profile.apply_env();
+
+    if let Err(e) = check_contract_version(&state) {
+        esay!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    if args.dry_run {
+        if let Err(e) = dry_run(&state, args.plan_json.as_deref()) {
+            esay!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    say!("╔══════════════════════════════════════════════════════════════╗");
+    say!("║       StakeVue V20 - Full Automation Bot                     ║");
+    say!("╠══════════════════════════════════════════════════════════════╣");
+    say!("║  User does:                                                  ║");
+    say!("║    • Stake    → Deposit CSPR, get stCSPR                     ║");
+    say!("║    • Unstake  → Request withdrawal                           ║");
+    say!("║    • Wait     → CSPR arrives automatically!                  ║");
+    say!("╠══════════════════════════════════════════════════════════════╣");
+    say!("║  Bot handles:                                                ║");
+    say!("║    ✓ Auto-delegate to validators                             ║");
+    say!("║    ✓ Auto-undelegate on unstake requests                     ║");
+    say!("║    ✓ Auto-add-liquidity after unbonding                      ║");
+    say!("║    ✓ Auto-claim and send CSPR to users                       ║");
+    say!("╚══════════════════════════════════════════════════════════════╝");
+    say!();
+    say!("Profile: {} ({})", state.profile.name, state.profile.chain_name);
+    say!("Contract: {:?}", state.contract);
+    say!("Validators: {}", state.validators.len());
+    say!("Check interval: {} seconds", CHECK_INTERVAL_SECS);
+    say!("State: {} (cycle {})", state_dir.display(), state.saved.cycles);
+    say!();
+
+    event("info", "bot_started", serde_json::json!({
+        "profile": state.profile.name,
+        "contract": format!("{:?}", state.contract),
+        "validators": state.validators.len(),
+        "cycle": state.saved.cycles,
+    }));
+
+    if let Some(addr) = &args.metrics_addr {
+        let max_age = Duration::from_secs(args.health_max_age_secs);
+        match telemetry::serve(addr, state.metrics.clone(), max_age) {
+            Ok(bound) => say!("Metrics: http://{}/metrics", bound),
+            Err(e) => {
+                esay!("Error: cannot serve metrics on {}: {}", addr, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    say!("Press Ctrl+C to stop");
+    say!("═══════════════════════════════════════════════════════════════");
+    say!();
 
     loop {
-        state.cycles += 1;
-        println!("┌─ Cycle {} ─────────────────────────────────────────────────", state.cycles);
+        state.saved.cycles += 1;
+        say!("┌─ Cycle {} ─────────────────────────────────────────────────", state.saved.cycles);
+
+        // RPC reads panic on node failures: fail the cycle, not the process
+        let result = guarded(|| run_cycle(&mut state));
+        {
+            let mut m = state.metrics.lock().unwrap_or_else(|e| e.into_inner());
+            m.cycles += 1;
+            match &result {
+                Ok(_) => m.last_success = Some(telemetry::unix_now()),
+                Err(_) => m.errors += 1,
+            }
+        }
+        let mut snapshot = std::mem::take(&mut state.cycle_snapshot);
+        snapshot.error = result.as_ref().err().cloned();
+        dispatch_alerts(&mut state, &snapshot);
 
-        match run_cycle(&mut state) {
+        match result {
             Ok(actions) => {
-                if actions == 0 {
-                    println!("│ ✓ No action needed");
+                if actions.is_empty() {
+                    say!("│ ✓ No action needed");
                 }
+                event("info", "cycle_completed", serde_json::json!({
+                    "cycle": state.saved.cycles,
+                    "actions": actions.len(),
+                }));
             }
             Err(e) => {
-                println!("│ ✗ Error: {}", e);
+                say!("│ ✗ Error: {}", e);
+                event("error", "cycle_failed", serde_json::json!({
+                    "cycle": state.saved.cycles,
+                    "error": e,
+                }));
             }
         }
 
-        println!("│");
-        println!("│ Total stats:");
-        println!("│   Delegated: {} CSPR", state.total_delegated / 1_000_000_000);
-        println!("│   Undelegated: {} CSPR", state.total_undelegated / 1_000_000_000);
-        println!("│   Claimed for users: {} CSPR", state.total_claimed / 1_000_000_000);
-        println!("└─ Next check in {} seconds", CHECK_INTERVAL_SECS);
-        println!();
+        say!("│");
+        say!("│ Total stats:");
+        say!("│   Delegated: {:#}", state.saved.total_delegated.cspr());
+        say!("│   Undelegated: {:#}", state.saved.total_undelegated.cspr());
+        say!("│   Added as liquidity: {:#}", state.saved.total_liquidity_added.cspr());
+        say!("│   Claimed for users: {:#}", state.saved.total_claimed.cspr());
+        if let Err(e) = state.save() {
+            say!("│ ✗ Error: {}", e);
+        }
+        say!("└─ Next check in {} seconds", CHECK_INTERVAL_SECS);
+        say!();
 
         thread::sleep(Duration::from_secs(CHECK_INTERVAL_SECS));
     }
 }
 
-fn run_cycle(state: &mut BotState) -> Result<u32, String> {
+/// Refuses contracts older than MIN_CONTRACT_VERSION, whose missing entry
+/// points would otherwise only fail mid-cycle.
+fn check_contract_version(state: &BotState) -> Result<(), String> {
     let env = odra_casper_livenet_env::env();
-    let address = Address::from_str(CONTRACT_HASH)
-        .map_err(|e| format!("Invalid contract hash: {:?}", e))?;
-    let mut contract = StakeVue::load(&env, address);
+    let contract = StakeVue::load(&env, state.contract);
+    let version = guarded(|| contract.try_get_contract_version().map_err(|e| format!("{:?}", e)))
+        .map_err(|e| format!(
+            "cannot read the contract version of {:?} ({}); the bot needs StakeVue V{} or later",
+            state.contract, e, MIN_CONTRACT_VERSION,
+        ))?;
+    if version < MIN_CONTRACT_VERSION {
+        return Err(format!(
+            "contract {:?} is StakeVue V{}, the bot needs V{} or later",
+            state.contract, version, MIN_CONTRACT_VERSION,
+        ));
+    }
+    Ok(())
+}
 
-    let mut actions = 0;
+fn dispatch_alerts(state: &mut BotState, snapshot: &CycleSnapshot) {
+    for notification in state.alerts.evaluate(snapshot) {
+        let firing = notification.status == AlertStatus::Firing;
+        say!("│ {} ALERT {} {}: {}",
+            if firing { "⚠" } else { "✓" },
+            notification.kind.name(),
+            notification.status.name(),
+            notification.message);
+        event(if firing { "warn" } else { "info" }, "alert", serde_json::json!({
+            "alert": notification.kind.name(),
+            "status": notification.status.name(),
+            "message": notification.message,
+        }));
 
-    // Get current state
-    let available_liquidity = contract.get_available_liquidity();
-    let pending_undelegations = contract.get_pending_undelegations();
-    let pending_withdrawals = contract.get_pending_withdrawals();
-    let next_request_id = contract.get_next_request_id();
+        if let Some(webhook) = &state.webhook {
+            if let Err(e) = webhook.send(&notification) {
+                say!("│ ✗ Alert delivery failed: {}", e);
+                event("error", "alert_delivery_failed", serde_json::json!({ "error": e }));
+                state.alerts.undeliverable(&notification);
+            }
+        }
+    }
+}
 
-    let liquidity_cspr = available_liquidity.as_u64() / 1_000_000_000;
-    let pending_undel_cspr = pending_undelegations.as_u64() / 1_000_000_000;
-    let pending_withdraw_cspr = pending_withdrawals.as_u64() / 1_000_000_000;
+/// Plans a single cycle, prints it (or logs it as one `dry_run` event)
+/// and optionally writes it as JSON. Nothing is submitted.
+fn dry_run(state: &BotState, plan_json: Option<&Path>) -> Result<(), String> {
+    say!("┌─ Dry run (no deploys are submitted) ──────────────────────────");
+    let env = odra_casper_livenet_env::env();
+    let contract = StakeVue::load(&env, state.contract);
+    let (pool, actions) = guarded(|| Ok(plan_cycle(state, &env, &contract)))?;
+    print_pool(&pool);
+    print_unbonding(state);
 
-    println!("│ Pool status:");
-    println!("│   Available liquidity: {} CSPR", liquidity_cspr);
-    println!("│   Pending undelegations: {} CSPR", pending_undel_cspr);
-    println!("│   Pending withdrawals: {} CSPR", pending_withdraw_cspr);
-    println!("│   Next request ID: {}", next_request_id);
+    let unfinished = state.journal.latest()
+        .map_err(|e| format!("Cannot read journal: {}", e))?
+        .into_iter()
+        .filter(|e| matches!(e.status, EntryStatus::Pending | EntryStatus::Unconfirmed))
+        .count();
+    if unfinished > 0 {
+        say!("│");
+        say!("│ ⚠ {} unfinished deploy(s) in the journal, the bot reconciles them first", unfinished);
+    }
 
-    // ========================================================================
-    // ACTION 1: Auto-delegate if pool >= 500 CSPR
-    // ========================================================================
-    if available_liquidity >= U512::from(MIN_DELEGATION) {
-        let validator_str = state.next_validator();
-        println!("│");
-        println!("│ → [DELEGATE] {} CSPR to {}...", liquidity_cspr, &validator_str[..12]);
+    say!("│");
+    if actions.is_empty() {
+        say!("│ ✓ No action needed");
+    }
+    for (i, action) in actions.iter().enumerate() {
+        say!("│ {}. {}", i + 1, action.describe(&state.profile));
+    }
+    say!("└─ {} action(s) planned", actions.len());
 
-        let validator = PublicKey::from_hex(validator_str)
-            .map_err(|e| format!("Invalid validator: {:?}", e))?;
+    let plan = serde_json::json!({
+        "profile": state.profile.name,
+        "contract": format!("{:?}", state.contract),
+        "pool": pool.to_json(),
+        "actions": actions.iter().map(Action::to_json).collect::<Vec<_>>(),
+    });
+    event("info", "dry_run", serde_json::json!({
+        "plan": plan,
+        "unfinished_deploys": unfinished,
+    }));
 
-        env.set_gas(GAS_DELEGATE);
-        contract.admin_delegate(validator, available_liquidity);
+    if let Some(path) = plan_json {
+        let json = serde_json::to_string_pretty(&plan).map_err(|e| e.to_string())?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+        say!("Plan written to {}", path.display());
+    }
+    Ok(())
+}
 
-        state.total_delegated += available_liquidity.as_u64();
-        actions += 1;
-        println!("│   ✓ Delegated!");
+/// Settles journal entries the previous run did not fold into its state.
+/// A pending entry means the bot stopped between submitting a deploy and
+/// seeing its outcome. Its deploy's execution result decides when the
+/// deploy can be found on chain, contract state otherwise.
+/// Runs at the start of every cycle until it succeeds once; no new action
+/// is taken before that.
+fn recover(state: &mut BotState, env: &HostEnv, contract: &StakeVueHostRef) -> Result<(), String> {
+    let entries = state.journal.latest().map_err(|e| format!("Cannot read journal: {}", e))?;
+
+    let applied = state.saved.journal_applied;
+    for entry in entries.into_iter().filter(|e| e.id > applied) {
+        let entry = if entry.status == EntryStatus::Pending {
+            let action = Action::from_json(&entry.action);
+            let deploy = action.as_ref().and_then(|action| find_deploy(state, env, action, &entry.action));
+            let (status, error) = match deploy.as_ref().map(|deploy| &deploy.outcome) {
+                Some(DeployOutcome::Succeeded) => (EntryStatus::Succeeded, None),
+                Some(DeployOutcome::Failed(e)) => (EntryStatus::Failed, Some(format!("deploy failed: {}", e))),
+                _ if action.as_ref().is_some_and(|action| action_landed(env, contract, action, &entry.action)) => {
+                    (EntryStatus::Succeeded, None)
+                }
+                _ => (EntryStatus::Failed, Some(String::from("not applied on chain, found after restart"))),
+            };
+            let entry = JournalEntry { deploy_hash: deploy.map(|deploy| deploy.hash), ..entry };
+            say!("│ Reconciled journal entry #{}: {:?}", entry.id, status);
+            event("warn", "journal_reconciled", serde_json::json!({
+                "entry": entry.id,
+                "action": entry.action,
+                "status": status,
+                "deploy_hash": entry.deploy_hash,
+            }));
+            state.journal.finish(&entry, status, error)
+                .map_err(|e| format!("Cannot write journal: {}", e))?
+        } else {
+            entry
+        };
+        state.settle(&entry)?;
     }
+    state.recovered = true;
+    Ok(())
+}
 
-    // ========================================================================
-    // ACTION 2: Auto-undelegate if there are pending undelegation requests
-    // ========================================================================
-    if pending_undelegations > U512::zero() {
-        for validator_str in VALIDATORS {
-            let validator = PublicKey::from_hex(validator_str)
-                .map_err(|e| format!("Invalid validator: {:?}", e))?;
+/// Settles unconfirmed entries whose effect has since shown up on chain,
+/// and fails the ones still invisible after UNCONFIRMED_GIVE_UP_SECS.
+fn recheck_unconfirmed(state: &mut BotState, env: &HostEnv, contract: &StakeVueHostRef) -> Result<(), String> {
+    let entries = state.journal.latest().map_err(|e| format!("Cannot read journal: {}", e))?;
+    for entry in entries.into_iter().filter(|e| e.status == EntryStatus::Unconfirmed) {
+        let landed = Action::from_json(&entry.action)
+            .is_some_and(|action| action_landed(env, contract, &action, &entry.action));
+        let (status, error) = if landed {
+            (EntryStatus::Succeeded, None)
+        } else if telemetry::unix_now() >= entry.timestamp.saturating_add(UNCONFIRMED_GIVE_UP_SECS) {
+            (EntryStatus::Failed, Some(String::from("effect never showed up on chain")))
+        } else {
+            continue;
+        };
+        say!("│ Re-checked unconfirmed journal entry #{}: {:?}", entry.id, status);
+        event("warn", "journal_reconciled", serde_json::json!({
+            "entry": entry.id,
+            "action": entry.action,
+            "status": status,
+        }));
+        let entry = state.journal.finish(&entry, status, error)
+            .map_err(|e| format!("Cannot write journal: {}", e))?;
+        state.settle(&entry)?;
+    }
+    Ok(())
+}
 
-            let delegated = contract.get_delegated_to_validator(validator.clone());
+/// The deploy that carried a journaled action, if the node has it in a
+/// block after the height recorded before submission.
+fn find_deploy(state: &BotState, env: &HostEnv, action: &Action, record: &serde_json::Value) -> Option<FoundDeploy> {
+    let after_height = record["submitted_after_height"].as_u64()?;
+    match state.node.find_deploy(&env.caller(), action.entry_point(), after_height) {
+        Ok(deploy) => deploy,
+        Err(e) => {
+            say!("│ ⚠ Cannot look up the deploy: {}", e);
+            None
+        }
+    }
+}
 
-            if delegated >= pending_undelegations {
-                println!("│");
-                println!("│ → [UNDELEGATE] {} CSPR from {}...", pending_undel_cspr, &validator_str[..12]);
+/// Whether a journaled action is visible in contract state.
+///
+/// Added liquidity is not checked against available_liquidity, which
+/// claims paid in the same cycle lower again. Crediting the purse lowers
+/// its surplus by the amount instead (claims and delegations leave the
+/// surplus alone), and admin_add_liquidity takes the amount from the
+/// owner's account.
+fn action_landed(env: &HostEnv, contract: &StakeVueHostRef, action: &Action, record: &serde_json::Value) -> bool {
+    let before = record["delegated_before_motes"].as_str()
+        .and_then(|v| U512::from_dec_str(v).ok());
+    match (action, before) {
+        (Action::Delegate { validator, amount }, Some(before)) => {
+            contract.get_delegated_to_validator(validator.clone()) >= before + *amount
+        }
+        (Action::Undelegate { validator, amount }, Some(before)) => {
+            contract.get_delegated_to_validator(validator.clone()) + *amount <= before
+        }
+        (Action::AddLiquidity { source: Source::Purse, amount, .. }, _) => {
+            let before = record_motes(record, "purse_surplus_before_motes").value();
+            contract.get_purse_surplus() + *amount <= before
+        }
+        (Action::AddLiquidity { source: Source::Owner, amount, .. }, _) => {
+            let before = record_motes(record, "owner_balance_before_motes").value();
+            env.balance_of(&env.caller()) + *amount <= before
+        }
+        (Action::Claim { request_id, .. }, _) => contract.is_withdrawal_claimed(*request_id),
+        _ => false,
+    }
+}
 
-                env.set_gas(GAS_UNDELEGATE);
-                contract.admin_undelegate(validator, pending_undelegations);
+// ============================================================================
+// ACTION PLAN
+// ============================================================================
 
-                state.total_undelegated += pending_undelegations.as_u64();
-                actions += 1;
-                println!("│   ✓ Undelegated! (unbonding ~7 eras)");
-                break;
-            }
+#[derive(Debug, Clone)]
+enum Action {
+    Delegate { validator: PublicKey, amount: U512 },
+    Undelegate { validator: PublicKey, amount: U512 },
+    /// Unbonded CSPR of the undelegation with journal id `undelegation`.
+    AddLiquidity { undelegation: u64, source: Source, amount: U512 },
+    Claim { request_id: u64, amount: U512 },
+}
+
+impl Action {
+    fn describe(&self, profile: &Profile) -> String {
+        match self {
+            Action::Delegate { validator, amount } => format!(
+                "[DELEGATE] {:#} to {}",
+                Motes(*amount).cspr(),
+                profile.validator_name(&validator.to_hex())
+            ),
+            Action::Undelegate { validator, amount } => format!(
+                "[UNDELEGATE] {:#} from {}",
+                Motes(*amount).cspr(),
+                profile.validator_name(&validator.to_hex())
+            ),
+            Action::AddLiquidity { undelegation, source, amount } => format!(
+                "[ADD-LIQUIDITY] {:#} unbonded by undelegation #{} (found in {})",
+                Motes(*amount).cspr(),
+                undelegation,
+                source.name()
+            ),
+            Action::Claim { request_id, amount } => format!(
+                "[AUTO-CLAIM] Request #{}: {:#} to user",
+                request_id,
+                Motes(*amount).cspr()
+            ),
+        }
+    }
+
+    /// Contract entry point the action's deploy calls.
+    fn entry_point(&self) -> &'static str {
+        match self {
+            Action::Delegate { .. } => "admin_delegate",
+            Action::Undelegate { .. } => "admin_undelegate",
+            Action::AddLiquidity { source: Source::Purse, .. } => "admin_credit_purse_liquidity",
+            Action::AddLiquidity { source: Source::Owner, .. } => "admin_add_liquidity",
+            Action::Claim { .. } => "admin_process_claim",
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Action::Delegate { .. } => "delegate",
+            Action::Undelegate { .. } => "undelegate",
+            Action::AddLiquidity { .. } => "add_liquidity",
+            Action::Claim { .. } => "claim",
+        }
+    }
+
+    /// Amounts are motes as decimal strings so they survive JSON tooling.
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Action::Delegate { validator, amount } => serde_json::json!({
+                "action": "delegate",
+                "validator": validator.to_hex(),
+                "amount_motes": amount.to_string(),
+            }),
+            Action::Undelegate { validator, amount } => serde_json::json!({
+                "action": "undelegate",
+                "validator": validator.to_hex(),
+                "amount_motes": amount.to_string(),
+            }),
+            Action::AddLiquidity { undelegation, source, amount } => serde_json::json!({
+                "action": "add_liquidity",
+                "undelegation": undelegation,
+                "source": source.name(),
+                "amount_motes": amount.to_string(),
+            }),
+            Action::Claim { request_id, amount } => serde_json::json!({
+                "action": "claim",
+                "request_id": request_id,
+                "amount_motes": amount.to_string(),
+            }),
+        }
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Action> {
+        let amount = U512::from_dec_str(value["amount_motes"].as_str()?).ok()?;
+        let validator = || PublicKey::from_hex(value["validator"].as_str()?).ok();
+        match value["action"].as_str()? {
+            "delegate" => Some(Action::Delegate { validator: validator()?, amount }),
+            "undelegate" => Some(Action::Undelegate { validator: validator()?, amount }),
+            "add_liquidity" => Some(Action::AddLiquidity {
+                undelegation: value["undelegation"].as_u64()?,
+                source: serde_json::from_value(value["source"].clone()).ok()?,
+                amount,
+            }),
+            "claim" => Some(Action::Claim { request_id: value["request_id"].as_u64()?, amount }),
+            _ => None,
         }
     }
+}
+
+struct PoolSnapshot {
+    total_pool: U512,
+    exchange_rate: U512,
+    /// Delegation per profile validator, in profile order.
+    delegated: Vec<(PublicKey, U512)>,
+    available_liquidity: U512,
+    pending_undelegations: U512,
+    pending_withdrawals: U512,
+    next_request_id: u64,
+    /// Ready requests the plan could not pay for lack of liquidity.
+    stuck_claims: u32,
+}
+
+impl PoolSnapshot {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "total_pool_motes": self.total_pool.to_string(),
+            "exchange_rate": self.exchange_rate.to_string(),
+            "available_liquidity_motes": self.available_liquidity.to_string(),
+            "pending_undelegations_motes": self.pending_undelegations.to_string(),
+            "pending_withdrawals_motes": self.pending_withdrawals.to_string(),
+            "next_request_id": self.next_request_id,
+            "stuck_claims": self.stuck_claims,
+        })
+    }
+}
+
+fn print_unbonding(state: &BotState) {
+    let pending = &state.saved.unbonding.pending;
+    if pending.is_empty() {
+        return;
+    }
+    say!("│ Unbonding:");
+    for unbonding in pending {
+        let ready_at = unbonding.undelegated_at.saturating_add(state.profile.unbonding.period_secs);
+        let wait = ready_at.saturating_sub(telemetry::unix_now());
+        say!("│   #{} {:#} from {} - {}",
+            unbonding.journal_id,
+            unbonding.amount.cspr(),
+            state.profile.validator_name(&unbonding.validator),
+            if wait == 0 { String::from("waiting for payout") } else { format!("~{} min left", wait.div_ceil(60)) });
+    }
+}
+
+/// Reads a motes amount stored as a decimal string in a journal record.
+fn record_motes(record: &serde_json::Value, key: &str) -> Motes {
+    record[key].as_str().and_then(|v| v.parse().ok()).unwrap_or_default()
+}
+
+fn print_pool(pool: &PoolSnapshot) {
+    say!("│ Pool status:");
+    say!("│   Available liquidity: {:#}", Motes(pool.available_liquidity).cspr());
+    say!("│   Pending undelegations: {:#}", Motes(pool.pending_undelegations).cspr());
+    say!("│   Pending withdrawals: {:#}", Motes(pool.pending_withdrawals).cspr());
+    say!("│   Next request ID: {}", pool.next_request_id);
+}
+
+/// Reads the pool and decides this cycle's actions without submitting
+/// anything. Liquidity spent by earlier actions is not available to later
+/// ones, matching what the contract sees when the plan is executed.
+fn plan_cycle(state: &BotState, env: &HostEnv, contract: &StakeVueHostRef) -> (PoolSnapshot, Vec<Action>) {
+    let pool = PoolSnapshot {
+        total_pool: contract.get_total_pool(),
+        exchange_rate: contract.get_exchange_rate(),
+        delegated: state.validators.iter()
+            .map(|v| (v.clone(), contract.get_delegated_to_validator(v.clone())))
+            .collect(),
+        available_liquidity: contract.get_available_liquidity(),
+        pending_undelegations: contract.get_pending_undelegations(),
+        pending_withdrawals: contract.get_pending_withdrawals(),
+        next_request_id: contract.get_next_request_id(),
+        stuck_claims: 0,
+    };
+    let mut actions = Vec::new();
+    let mut liquidity = pool.available_liquidity;
 
     // ========================================================================
-    // ACTION 3: Auto-claim ready withdrawals for users
+    // ACTION 1: Add unbonded CSPR back to pool liquidity
     // ========================================================================
-    // Iterate through all request IDs and process ready ones
-    for request_id in 1..next_request_id {
-        // Check if this request is ready and not claimed
+    // Payouts to the purse are credited in place; payouts to the owner's
+    // account are sent back with admin_add_liquidity
+    let balances = read_balances(env, contract);
+    let mut owner_funds = balances.owner.value();
+    let gas = U512::from(GasSettings::motes(state.gas().admin));
+    let landed = state.saved.unbonding.landed(telemetry::unix_now(), &state.profile.unbonding, balances);
+    for (unbonding, source) in landed {
+        let amount = unbonding.amount.value();
+        let cost = match source {
+            Source::Purse => gas,
+            Source::Owner => amount + gas,
+        };
+        if owner_funds < cost {
+            say!("│ ⚠ Owner balance too low to add {:#} of unbonded CSPR", unbonding.amount.cspr());
+            break;
+        }
+        owner_funds -= cost;
+        actions.push(Action::AddLiquidity {
+            undelegation: unbonding.journal_id,
+            source,
+            amount,
+        });
+        liquidity += amount;
+    }
+
+    // ========================================================================
+    // ACTION 2: Auto-claim ready withdrawals for users
+    // ========================================================================
+    let mut stuck_claims = 0;
+    for request_id in 1..pool.next_request_id {
         let amount = contract.get_withdrawal_amount(request_id);
-        let is_ready = contract.is_withdrawal_ready(request_id);
-        let is_claimed = contract.is_withdrawal_claimed(request_id);
+        if amount > U512::zero()
+            && contract.is_withdrawal_ready(request_id)
+            && !contract.is_withdrawal_claimed(request_id)
+        {
+            if liquidity >= amount {
+                actions.push(Action::Claim { request_id, amount });
+                liquidity -= amount;
+            } else {
+                stuck_claims += 1;
+            }
+        }
+    }
 
-        if amount > U512::zero() && is_ready && !is_claimed {
-            // Check if we have enough liquidity
-            let current_liquidity = contract.get_available_liquidity();
-            if current_liquidity >= amount {
-                let amount_cspr = amount.as_u64() / 1_000_000_000;
-                println!("│");
-                println!("│ → [AUTO-CLAIM] Request #{}: {} CSPR to user...", request_id, amount_cspr);
+    // ========================================================================
+    // ACTION 3: Auto-undelegate pending unstake demand across validators
+    // ========================================================================
+    // Planned on the delegations as they are before this cycle's delegation
+    let min_delegation = Motes::from_cspr(MIN_DELEGATION_CSPR).value();
+    if pool.pending_undelegations > U512::zero() {
+        let planned = plan_undelegations(&validator_positions(contract), pool.pending_undelegations, min_delegation);
+        for undelegation in planned {
+            actions.push(Action::Undelegate {
+                validator: undelegation.validator,
+                amount: undelegation.amount,
+            });
+        }
+    }
 
-                env.set_gas(GAS_CLAIM);
-                contract.admin_process_claim(request_id);
+    // ========================================================================
+    // ACTION 4: Auto-delegate what is left if >= 500 CSPR
+    // ========================================================================
+    // To the next validator in round-robin order with room for it, at most
+    // up to its cap and concentration limit
+    if liquidity >= min_delegation {
+        let target = state.round_robin()
+            .map(|validator| (validator.clone(), contract.get_validator_headroom(validator.clone())))
+            .find(|(_, headroom)| *headroom >= min_delegation);
+        match target {
+            Some((validator, headroom)) => actions.push(Action::Delegate {
+                validator,
+                amount: liquidity.min(headroom),
+            }),
+            None => say!("│ ⚠ Every validator is at its delegation limit"),
+        }
+    }
+
+    (PoolSnapshot { stuck_claims, ..pool }, actions)
+}
+
+/// Contract purse surplus (what admin_credit_purse_liquidity may credit)
+/// and the owner's (caller's) balance, for finding unbonded CSPR.
+fn read_balances(env: &HostEnv, contract: &StakeVueHostRef) -> Balances {
+    Balances {
+        purse_surplus: Motes(contract.get_purse_surplus()),
+        owner: Motes(env.balance_of(&env.caller())),
+    }
+}
+
+/// Stores balances in a journal record, read back with record_motes.
+fn record_balances(record: &mut serde_json::Value, balances: Balances) {
+    record["purse_surplus_before_motes"] = balances.purse_surplus.to_string().into();
+    record["owner_balance_before_motes"] = balances.owner.to_string().into();
+}
 
-                state.total_claimed += amount.as_u64();
-                actions += 1;
-                println!("│   ✓ Claimed and sent to user!");
+/// Every validator the contract has delegated to, including removed ones
+/// that still hold stake, not just the profile's round-robin set.
+fn validator_positions(contract: &StakeVueHostRef) -> Vec<ValidatorPosition> {
+    (0..contract.get_validator_count())
+        .filter_map(|index| contract.get_validator(index))
+        .map(|validator| ValidatorPosition {
+            delegated: contract.get_delegated_to_validator(validator.clone()),
+            active: contract.is_validator_active(validator.clone()),
+            max_allocation: contract.get_validator_info(validator.clone())
+                .map(|info| info.max_allocation)
+                .unwrap_or_default(),
+            validator,
+        })
+        .filter(|position| !position.delegated.is_zero())
+        .collect()
+}
+
+fn run_cycle(state: &mut BotState) -> Result<Vec<Action>, String> {
+    let env = odra_casper_livenet_env::env();
+    let mut contract = StakeVue::load(&env, state.contract);
+    // Separate handle for confirmation queries while `contract` submits
+    let reader = StakeVue::load(&env, state.contract);
+    let policy = RetryPolicy::default();
+
+    if !state.recovered {
+        recover(state, &env, &reader)
+            .map_err(|e| format!("recovery failed, refusing to take new actions: {}", e))?;
+    }
+    recheck_unconfirmed(state, &env, &reader)?;
+    let (pool, actions) = plan_cycle(state, &env, &contract);
+    print_pool(&pool);
+    print_unbonding(state);
+    state.update_metrics(&pool);
+    state.cycle_snapshot = CycleSnapshot {
+        exchange_rate: Some(pool.exchange_rate),
+        stuck_claims: Some(pool.stuck_claims),
+        error: None,
+    };
+    event("info", "pool_snapshot", pool.to_json());
+
+    for action in &actions {
+        say!("│");
+        say!("│ → {}...", action.describe(&state.profile));
+        event("info", "action_submitted", action.to_json());
+
+        // Journal before submitting so a crash mid-deploy can be reconciled
+        let mut record = action.to_json();
+        match action {
+            Action::Delegate { validator, .. } => {
+                record["delegated_before_motes"] =
+                    contract.get_delegated_to_validator(validator.clone()).to_string().into();
+            }
+            Action::Undelegate { validator, .. } => {
+                record["delegated_before_motes"] =
+                    contract.get_delegated_to_validator(validator.clone()).to_string().into();
+                record_balances(&mut record, read_balances(&env, &contract));
+            }
+            Action::AddLiquidity { .. } => {
+                record_balances(&mut record, read_balances(&env, &contract));
+            }
+            Action::Claim { .. } => {}
+        }
+        match state.node.block_height() {
+            Ok(height) => record["submitted_after_height"] = height.into(),
+            Err(e) => say!("│ ⚠ Cannot read the block height, the deploy won't be looked up: {}", e),
+        }
+        let entry = state.journal.begin(record.clone()).map_err(|e| format!("Cannot write journal: {}", e))?;
+
+        let landed = || action_landed(&env, &reader, action, &record);
+        let result = match action {
+            Action::Delegate { validator, amount } => {
+                let gas = GasSettings::motes(state.gas().delegate);
+                submit(&policy, || {
+                    env.set_gas(gas);
+                    contract.try_admin_delegate(validator.clone(), *amount)
+                }, landed)
+            }
+            Action::Undelegate { validator, amount } => {
+                let gas = GasSettings::motes(state.gas().undelegate);
+                submit(&policy, || {
+                    env.set_gas(gas);
+                    contract.try_admin_undelegate(validator.clone(), *amount)
+                }, landed)
+            }
+            Action::AddLiquidity { source: Source::Purse, amount, .. } => {
+                let gas = GasSettings::motes(state.gas().admin);
+                submit(&policy, || {
+                    env.set_gas(gas);
+                    contract.try_admin_credit_purse_liquidity(*amount)
+                }, landed)
+            }
+            Action::AddLiquidity { source: Source::Owner, amount, .. } => {
+                let gas = GasSettings::motes(state.gas().admin);
+                submit(&policy, || {
+                    env.set_gas(gas);
+                    contract.with_tokens(*amount).try_admin_add_liquidity()
+                }, landed)
+            }
+            Action::Claim { request_id, .. } => {
+                let gas = GasSettings::motes(state.gas().admin);
+                submit(&policy, || {
+                    env.set_gas(gas);
+                    contract.try_admin_process_claim(*request_id)
+                }, landed)
             }
+        };
+
+        let (status, error) = match &result {
+            Ok(_) => (EntryStatus::Succeeded, None),
+            Err(e @ SubmitError::Unconfirmed) => (EntryStatus::Unconfirmed, Some(e.to_string())),
+            Err(e) => (EntryStatus::Failed, Some(e.to_string())),
+        };
+        let deploy = find_deploy(state, &env, action, &record);
+        let entry = JournalEntry { deploy_hash: deploy.map(|deploy| deploy.hash), ..entry };
+        let entry = state.journal.finish(&entry, status, error)
+            .map_err(|e| format!("Cannot write journal: {}", e))?;
+        state.settle(&entry)?;
+
+        let attempts = match result {
+            Ok(attempts) => attempts,
+            Err(e) => {
+                event("error", "action_failed", serde_json::json!({
+                    "action": action.to_json(),
+                    "error": e.to_string(),
+                    "deploy_hash": entry.deploy_hash,
+                }));
+                return Err(e.to_string());
+            }
+        };
+        if attempts > 1 {
+            say!("│   (confirmed after {} attempts)", attempts);
+        }
+        state.metrics.lock().unwrap_or_else(|e| e.into_inner())
+            .record_action(action.kind());
+        event("info", "action_confirmed", serde_json::json!({
+            "action": action.to_json(),
+            "attempts": attempts,
+            "deploy_hash": entry.deploy_hash,
+        }));
+        match action {
+            Action::Delegate { .. } => say!("│   ✓ Delegated!"),
+            Action::Undelegate { .. } => say!("│   ✓ Undelegated! (unbonding ~7 eras)"),
+            Action::AddLiquidity { .. } => say!("│   ✓ Added to pool liquidity!"),
+            Action::Claim { .. } => say!("│   ✓ Claimed and sent to user!"),
         }
     }
 
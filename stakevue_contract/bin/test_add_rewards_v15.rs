@@ -1,7 +0,0 @@
-//! DEPRECATED: V15 add_rewards is replaced by V17 harvest_rewards
-//! This script is kept for reference only.
-
-fn main() {
-    println!("=== DEPRECATED ===");
-    println!("V15 add_rewards has been replaced by V17 harvest_rewards.");
-}
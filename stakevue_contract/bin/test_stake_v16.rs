@@ -1,12 +0,0 @@
-//! DEPRECATED: V16 is replaced by V17 (Multi-Validator)
-//! Use: cargo run --bin test_stake_v17 --features livenet
-//!
-//! This script is kept for reference only.
-
-fn main() {
-    println!("=== DEPRECATED ===");
-    println!("V16 single-validator support has been replaced by V17 multi-validator.");
-    println!();
-    println!("Use instead:");
-    println!("  cargo run --bin test_stake_v17 --features livenet");
-}
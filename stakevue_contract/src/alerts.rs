@@ -0,0 +1,292 @@
+//! Alert rules over the bot's cycle snapshots, dispatched to a webhook.
+//!
+//! Rules are configured per profile under `[profiles.<name>.alerts]`; a rule
+//! is enabled by setting its threshold. Each rule notifies once when it
+//! starts firing and once when it recovers, not on every cycle in between.
+//!
+//! ```toml
+//! [profiles.testnet.alerts]
+//! webhook_url = "https://hooks.example.com/stakevue"
+//! stuck_claims_cycles = 3      # ready claims unpaid for lack of liquidity
+//! max_rate_drop_bps = 10       # exchange rate below its peak
+//! max_consecutive_errors = 3   # cycles in a row ending with an error
+//! ```
+
+use std::collections::BTreeSet;
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::format;
+
+use odra::casper_types::U512;
+use serde::Deserialize;
+
+use crate::amounts::format_units;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AlertSettings {
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub stuck_claims_cycles: Option<u32>,
+    #[serde(default)]
+    pub max_rate_drop_bps: Option<u32>,
+    #[serde(default)]
+    pub max_consecutive_errors: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertKind {
+    StuckClaims,
+    ExchangeRateDrop,
+    BotErrors,
+}
+
+impl AlertKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertKind::StuckClaims => "stuck_claims",
+            AlertKind::ExchangeRateDrop => "exchange_rate_drop",
+            AlertKind::BotErrors => "bot_errors",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+impl AlertStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertStatus::Firing => "firing",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: AlertKind,
+    pub status: AlertStatus,
+    pub message: String,
+}
+
+/// What one bot cycle observed. Pool fields are `None` when the cycle
+/// failed before reading the pool.
+#[derive(Debug, Clone, Default)]
+pub struct CycleSnapshot {
+    pub exchange_rate: Option<U512>,
+    /// Ready withdrawal requests left unpaid for lack of liquidity.
+    pub stuck_claims: Option<u32>,
+    pub error: Option<String>,
+}
+
+pub struct AlertEngine {
+    settings: AlertSettings,
+    peak_rate: U512,
+    stuck_streak: u32,
+    error_streak: u32,
+    active: BTreeSet<AlertKind>,
+}
+
+impl AlertEngine {
+    pub fn new(settings: AlertSettings) -> Self {
+        Self {
+            settings,
+            peak_rate: U512::zero(),
+            stuck_streak: 0,
+            error_streak: 0,
+            active: BTreeSet::new(),
+        }
+    }
+
+    pub fn webhook_url(&self) -> Option<&str> {
+        self.settings.webhook_url.as_deref()
+    }
+
+    /// Updates the rules with a cycle and returns the state changes.
+    pub fn evaluate(&mut self, snapshot: &CycleSnapshot) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+
+        if let Some(cycles) = self.settings.stuck_claims_cycles {
+            if let Some(stuck) = snapshot.stuck_claims {
+                self.stuck_streak = if stuck > 0 { self.stuck_streak + 1 } else { 0 };
+                let message = format!(
+                    "{} ready claim(s) unpaid for {} cycle(s) (InsufficientLiquidity)",
+                    stuck, self.stuck_streak
+                );
+                self.transition(AlertKind::StuckClaims, self.stuck_streak >= cycles.max(1), message, &mut notifications);
+            }
+        }
+
+        if let Some(max_drop) = self.settings.max_rate_drop_bps {
+            if let Some(rate) = snapshot.exchange_rate {
+                self.peak_rate = self.peak_rate.max(rate);
+                let drop_bps = if self.peak_rate.is_zero() {
+                    U512::zero()
+                } else {
+                    (self.peak_rate - rate) * U512::from(10_000u64) / self.peak_rate
+                };
+                let message = format!(
+                    "exchange rate {} is {} bps below its peak {}",
+                    format_units(rate), drop_bps, format_units(self.peak_rate)
+                );
+                self.transition(AlertKind::ExchangeRateDrop, drop_bps > U512::from(max_drop), message, &mut notifications);
+            }
+        }
+
+        if let Some(max_errors) = self.settings.max_consecutive_errors {
+            self.error_streak = if snapshot.error.is_some() { self.error_streak + 1 } else { 0 };
+            let message = match &snapshot.error {
+                Some(e) => format!("{} consecutive failed cycle(s), last: {}", self.error_streak, e),
+                None => String::from("cycles succeeding again"),
+            };
+            self.transition(AlertKind::BotErrors, self.error_streak >= max_errors.max(1), message, &mut notifications);
+        }
+
+        notifications
+    }
+
+    /// Reverts a notification that could not be delivered, so the next
+    /// evaluation produces it again.
+    pub fn undeliverable(&mut self, notification: &Notification) {
+        match notification.status {
+            AlertStatus::Firing => self.active.remove(&notification.kind),
+            AlertStatus::Resolved => self.active.insert(notification.kind),
+        };
+    }
+
+    fn transition(&mut self, kind: AlertKind, firing: bool, message: String, out: &mut Vec<Notification>) {
+        let status = match (firing, self.active.contains(&kind)) {
+            (true, false) => AlertStatus::Firing,
+            (false, true) => AlertStatus::Resolved,
+            _ => return,
+        };
+        if firing {
+            self.active.insert(kind);
+        } else {
+            self.active.remove(&kind);
+        }
+        out.push(Notification { kind, status, message });
+    }
+}
+
+/// POSTs notifications as JSON:
+/// `{"alert":..,"status":"firing"|"resolved","profile":..,"message":..,"timestamp":..}`.
+pub struct Webhook {
+    url: String,
+    profile: String,
+    agent: ureq::Agent,
+}
+
+impl Webhook {
+    pub fn new(url: &str, profile: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(std::time::Duration::from_secs(10))
+            .build();
+        Self { url: url.to_string(), profile: profile.to_string(), agent }
+    }
+
+    pub fn send(&self, notification: &Notification) -> Result<(), String> {
+        let payload = serde_json::json!({
+            "alert": notification.kind.name(),
+            "status": notification.status.name(),
+            "profile": self.profile,
+            "message": notification.message,
+            "timestamp": crate::telemetry::unix_now(),
+        });
+        self.agent
+            .post(&self.url)
+            .send_json(payload)
+            .map(|_| ())
+            .map_err(|e| format!("webhook {}: {}", self.url, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    fn snapshot(rate: u64, stuck: u32, error: bool) -> CycleSnapshot {
+        CycleSnapshot {
+            exchange_rate: Some(U512::from(rate)),
+            stuck_claims: Some(stuck),
+            error: error.then(|| String::from("rpc timeout")),
+        }
+    }
+
+    #[test]
+    fn test_alerts_fire_once_and_recover() {
+        let mut engine = AlertEngine::new(AlertSettings {
+            webhook_url: None,
+            stuck_claims_cycles: Some(2),
+            max_rate_drop_bps: Some(10),
+            max_consecutive_errors: Some(1),
+        });
+
+        assert!(engine.evaluate(&snapshot(1_000_000_000, 1, false)).is_empty());
+        let fired = engine.evaluate(&snapshot(1_000_000_000, 1, false));
+        assert_eq!(fired.len(), 1);
+        assert_eq!((fired[0].kind, fired[0].status), (AlertKind::StuckClaims, AlertStatus::Firing));
+
+        // Still stuck: no repeat. Rate drops 0.2% and a cycle fails.
+        let fired = engine.evaluate(&snapshot(998_000_000, 2, true));
+        let kinds: Vec<_> = fired.iter().map(|n| (n.kind, n.status)).collect();
+        assert_eq!(kinds, [
+            (AlertKind::ExchangeRateDrop, AlertStatus::Firing),
+            (AlertKind::BotErrors, AlertStatus::Firing),
+        ]);
+
+        let resolved = engine.evaluate(&snapshot(1_000_000_000, 0, false));
+        assert_eq!(resolved.len(), 3);
+        assert!(resolved.iter().all(|n| n.status == AlertStatus::Resolved));
+
+        // An undelivered notification is produced again
+        let fired = engine.evaluate(&snapshot(1_000_000_000, 0, true));
+        engine.undeliverable(&fired[0]);
+        assert_eq!(engine.evaluate(&snapshot(1_000_000_000, 0, true)).len(), 1);
+    }
+
+    #[test]
+    fn test_webhook_posts_to_local_stub() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = std::vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader.get_mut().write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+            tx.send(String::from_utf8(body).unwrap()).unwrap();
+        });
+
+        let webhook = Webhook::new(&url, "testnet");
+        webhook.send(&Notification {
+            kind: AlertKind::StuckClaims,
+            status: AlertStatus::Firing,
+            message: String::from("2 ready claim(s) unpaid"),
+        }).unwrap();
+
+        let body: serde_json::Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+        assert_eq!(body["alert"], "stuck_claims");
+        assert_eq!(body["status"], "firing");
+        assert_eq!(body["profile"], "testnet");
+    }
+}
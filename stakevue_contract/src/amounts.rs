@@ -0,0 +1,304 @@
+//! Exact CSPR amounts for the binaries.
+//!
+//! [`Motes`] is the integer amount the contract works in; [`Cspr`] is the
+//! same amount read and written as a decimal CSPR string ("1.5", "0.000000001").
+//! Parsing never rounds and conversions never truncate: anything that does
+//! not fit is an [`AmountError`]. stCSPR uses the same 9 decimals, and so
+//! does the contract's exchange rate, so [`format_units`] / [`parse_units`]
+//! cover those too.
+
+use core::fmt;
+use core::str::FromStr;
+use alloc::format;
+use alloc::string::String;
+use odra::casper_types::{U256, U512};
+
+pub const CSPR_DECIMALS: usize = 9;
+pub const MOTES_PER_CSPR: u64 = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountError {
+    Empty,
+    InvalidDigit,
+    TooManyDecimals,
+    Overflow,
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountError::Empty => write!(f, "empty amount"),
+            AmountError::InvalidDigit => write!(f, "amount must be a non-negative decimal number"),
+            AmountError::TooManyDecimals => write!(f, "amount has more than {} decimals", CSPR_DECIMALS),
+            AmountError::Overflow => write!(f, "amount out of range"),
+        }
+    }
+}
+
+impl core::error::Error for AmountError {}
+
+/// Integer amount in motes (1 CSPR = 10^9 motes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Motes(pub U512);
+
+impl Motes {
+    pub fn zero() -> Self {
+        Motes(U512::zero())
+    }
+
+    pub fn from_cspr(whole: u64) -> Self {
+        Motes(U512::from(whole) * U512::from(MOTES_PER_CSPR))
+    }
+
+    pub fn value(self) -> U512 {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn checked_add(self, other: Motes) -> Option<Motes> {
+        self.0.checked_add(other.0).map(Motes)
+    }
+
+    pub fn checked_sub(self, other: Motes) -> Option<Motes> {
+        self.0.checked_sub(other.0).map(Motes)
+    }
+
+    /// Adds, clamping at `U512::MAX` (for running totals).
+    pub fn saturating_add(self, other: Motes) -> Motes {
+        Motes(self.0.saturating_add(other.0))
+    }
+
+    pub fn to_u64(self) -> Result<u64, AmountError> {
+        if self.0 > U512::from(u64::MAX) {
+            return Err(AmountError::Overflow);
+        }
+        Ok(self.0.as_u64())
+    }
+
+    /// For stCSPR amounts, which the token stores as U256.
+    pub fn to_u256(self) -> Result<U256, AmountError> {
+        let mut bytes = [0u8; 64];
+        self.0.to_little_endian(&mut bytes);
+        if bytes[32..].iter().any(|b| *b != 0) {
+            return Err(AmountError::Overflow);
+        }
+        Ok(U256::from_little_endian(&bytes[..32]))
+    }
+
+    pub fn from_u256(value: U256) -> Self {
+        let mut bytes = [0u8; 64];
+        value.to_little_endian(&mut bytes[..32]);
+        Motes(U512::from_little_endian(&bytes))
+    }
+
+    pub fn cspr(self) -> Cspr {
+        Cspr(self)
+    }
+}
+
+impl From<U512> for Motes {
+    fn from(value: U512) -> Self {
+        Motes(value)
+    }
+}
+
+impl From<Motes> for U512 {
+    fn from(value: Motes) -> Self {
+        value.0
+    }
+}
+
+/// Plain integer motes, e.g. "1500000000".
+impl FromStr for Motes {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_units_with(s, 0).map(Motes)
+    }
+}
+
+impl fmt::Display for Motes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An amount read and written in decimal CSPR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Cspr(pub Motes);
+
+impl Cspr {
+    pub fn motes(self) -> Motes {
+        self.0
+    }
+}
+
+impl From<Motes> for Cspr {
+    fn from(value: Motes) -> Self {
+        Cspr(value)
+    }
+}
+
+impl From<Cspr> for Motes {
+    fn from(value: Cspr) -> Self {
+        value.0
+    }
+}
+
+/// Decimal CSPR, e.g. "1000", "1.5", "0.000000001".
+impl FromStr for Cspr {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_units(s).map(|v| Cspr(Motes(v)))
+    }
+}
+
+/// Shortest exact decimal form, e.g. "1.5"; `{:#}` appends " CSPR".
+impl fmt::Display for Cspr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_units(self.0 .0))?;
+        if f.alternate() {
+            write!(f, " CSPR")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a decimal string with up to 9 decimals into base units.
+pub fn parse_units(s: &str) -> Result<U512, AmountError> {
+    parse_units_with(s, CSPR_DECIMALS)
+}
+
+/// Formats base units as a decimal with 9 places, trailing zeros trimmed.
+pub fn format_units(value: U512) -> String {
+    let unit = U512::from(MOTES_PER_CSPR);
+    let whole = value / unit;
+    let fraction = (value % unit).as_u64();
+    if fraction == 0 {
+        return format!("{}", whole);
+    }
+    let digits = format!("{:0width$}", fraction, width = CSPR_DECIMALS);
+    format!("{}.{}", whole, digits.trim_end_matches('0'))
+}
+
+fn parse_units_with(s: &str, decimals: usize) -> Result<U512, AmountError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(AmountError::Empty);
+    }
+    let (whole, fraction) = match s.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (s, ""),
+    };
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(AmountError::InvalidDigit);
+    }
+    if !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(AmountError::InvalidDigit);
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals {
+        return Err(AmountError::TooManyDecimals);
+    }
+
+    let mut value = U512::zero();
+    let digits = whole.bytes().chain(fraction.bytes());
+    let scale = decimals - fraction.len();
+    for digit in digits.chain(core::iter::repeat(b'0').take(scale)) {
+        value = value
+            .checked_mul(U512::from(10u8))
+            .and_then(|v| v.checked_add(U512::from(digit - b'0')))
+            .ok_or(AmountError::Overflow)?;
+    }
+    Ok(value)
+}
+
+#[cfg(feature = "livenet")]
+mod serde_impls {
+    use super::*;
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::{Deserialize, Serialize, Serializer};
+
+    /// Motes serialize as decimal strings so JSON consumers keep every digit.
+    impl Serialize for Motes {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&self.0)
+        }
+    }
+
+    /// Accepts decimal strings and plain integers.
+    impl<'de> Deserialize<'de> for Motes {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct MotesVisitor;
+
+            impl Visitor<'_> for MotesVisitor {
+                type Value = Motes;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "an amount in motes")
+                }
+
+                fn visit_u64<E: de::Error>(self, v: u64) -> Result<Motes, E> {
+                    Ok(Motes(U512::from(v)))
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Motes, E> {
+                    v.parse().map_err(E::custom)
+                }
+            }
+
+            deserializer.deserialize_any(MotesVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cspr(s: &str) -> Result<U512, AmountError> {
+        s.parse::<Cspr>().map(|c| c.motes().value())
+    }
+
+    #[test]
+    fn test_parse_and_format_cspr_exactly() {
+        assert_eq!(cspr("1000"), Ok(U512::from(1_000_000_000_000u64)));
+        assert_eq!(cspr("1.5"), Ok(U512::from(1_500_000_000u64)));
+        assert_eq!(cspr(".5"), Ok(U512::from(500_000_000u64)));
+        assert_eq!(cspr("0.000000001"), Ok(U512::one()));
+        assert_eq!(cspr("2.100000000000"), Ok(U512::from(2_100_000_000u64)));
+        assert_eq!(cspr("0.0000000001"), Err(AmountError::TooManyDecimals));
+        assert_eq!(cspr("-1"), Err(AmountError::InvalidDigit));
+        assert_eq!(cspr("1e9"), Err(AmountError::InvalidDigit));
+        assert_eq!(cspr("."), Err(AmountError::InvalidDigit));
+        assert_eq!(cspr(" "), Err(AmountError::Empty));
+
+        let huge = "1".repeat(160);
+        assert_eq!(cspr(&huge), Err(AmountError::Overflow));
+
+        assert_eq!(format!("{}", Motes(U512::from(1_500_000_000u64)).cspr()), "1.5");
+        assert_eq!(format!("{:#}", Motes::from_cspr(500).cspr()), "500 CSPR");
+        assert_eq!(format_units(U512::from(1_000_000_001u64)), "1.000000001");
+
+        // Round trip beyond u64
+        let big = U512::from(u64::MAX) * U512::from(1_000u64) + U512::from(7u64);
+        assert_eq!(cspr(&format_units(big)), Ok(big));
+    }
+
+    #[test]
+    fn test_conversions_are_checked() {
+        let max_u256 = Motes::from_u256(U256::MAX);
+        assert_eq!(max_u256.to_u256(), Ok(U256::MAX));
+        assert_eq!(max_u256.checked_add(Motes(U512::one())).unwrap().to_u256(), Err(AmountError::Overflow));
+
+        assert_eq!(Motes(U512::from(u64::MAX)).to_u64(), Ok(u64::MAX));
+        assert_eq!(Motes(U512::from(u64::MAX) + 1).to_u64(), Err(AmountError::Overflow));
+        assert_eq!(Motes::zero().checked_sub(Motes(U512::one())), None);
+        assert_eq!("1500000000".parse::<Motes>(), Ok(Motes(U512::from(1_500_000_000u64))));
+        assert_eq!("1.5".parse::<Motes>(), Err(AmountError::TooManyDecimals));
+    }
+}
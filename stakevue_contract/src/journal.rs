@@ -0,0 +1,175 @@
+//! Append-only action journal for the livenet binaries.
+//!
+//! Every deploy is recorded as a `pending` line before it is submitted and
+//! followed by a `succeeded` / `failed` line with the same id once the
+//! outcome is known. A deploy that executed without its effect showing up
+//! is `unconfirmed` until a later check settles it either way. A pending
+//! entry without an outcome means the process died mid-submission; callers
+//! reconcile it before acting again. One JSON object per line, fsynced on
+//! every append.
+//!
+//! Odra's `HostRef` calls do not return the hash of the deploy they sent,
+//! so the caller finds it on chain afterwards (see `node`) and records it
+//! with the outcome. A pending entry has no hash yet; its action carries
+//! the block height it was submitted after, which lets reconciliation look
+//! the deploy up and go by its execution result.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::string::String;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::vec::Vec;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+    /// Executed, but its effect was not visible on chain yet.
+    Unconfirmed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: u64,
+    /// Unix time in seconds when the line was written.
+    pub timestamp: u64,
+    pub status: EntryStatus,
+    pub action: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Hash of the deploy that carried the action, once found on chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_hash: Option<String>,
+}
+
+pub struct Journal {
+    path: PathBuf,
+    next_id: u64,
+}
+
+impl Journal {
+    /// Opens (or creates) the journal at `path`.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        terminate_torn_line(path)?;
+        let mut journal = Self { path: path.to_path_buf(), next_id: 1 };
+        journal.next_id = journal.read_all()?.iter().map(|e| e.id + 1).max().unwrap_or(1);
+        Ok(journal)
+    }
+
+    /// Records an action as pending and returns its entry.
+    pub fn begin(&mut self, action: serde_json::Value) -> io::Result<JournalEntry> {
+        let entry = JournalEntry {
+            id: self.next_id,
+            timestamp: now(),
+            status: EntryStatus::Pending,
+            action,
+            error: None,
+            deploy_hash: None,
+        };
+        self.append(&entry)?;
+        self.next_id += 1;
+        Ok(entry)
+    }
+
+    /// Records the outcome of a pending or unconfirmed entry, keeping the
+    /// entry's deploy hash.
+    pub fn finish(&mut self, entry: &JournalEntry, status: EntryStatus, error: Option<String>) -> io::Result<JournalEntry> {
+        let outcome = JournalEntry {
+            timestamp: now(),
+            status,
+            error,
+            ..entry.clone()
+        };
+        self.append(&outcome)?;
+        Ok(outcome)
+    }
+
+    /// Latest record per entry id, in id order.
+    pub fn latest(&self) -> io::Result<Vec<JournalEntry>> {
+        let mut by_id = BTreeMap::new();
+        for entry in self.read_all()? {
+            by_id.insert(entry.id, entry);
+        }
+        Ok(by_id.into_values().collect())
+    }
+
+    fn append(&self, entry: &JournalEntry) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+        writeln!(file, "{}", line)?;
+        file.sync_all()
+    }
+
+    /// A torn last line from a crash mid-write is skipped.
+    fn read_all(&self) -> io::Result<Vec<JournalEntry>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(entry) = serde_json::from_str(&line?) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Ends a line left unterminated by a crash so the next append starts clean.
+fn terminate_torn_line(path: &Path) -> io::Result<()> {
+    match std::fs::read(path) {
+        Ok(bytes) if bytes.last().is_some_and(|b| *b != b'\n') => {
+            OpenOptions::new().append(true).open(path)?.write_all(b"\n")
+        }
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_tracks_unfinished_entries_across_reopen() {
+        let path = std::env::temp_dir().join(std::format!("stakevue-journal-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut journal = Journal::open(&path).unwrap();
+        let first = journal.begin(serde_json::json!({ "action": "claim", "request_id": 1 })).unwrap();
+        let first = JournalEntry { deploy_hash: Some(String::from("ab12")), ..first };
+        journal.finish(&first, EntryStatus::Succeeded, None).unwrap();
+        journal.begin(serde_json::json!({ "action": "claim", "request_id": 2 })).unwrap();
+
+        // Simulate a crash mid-write
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"id\":3,").unwrap();
+
+        let mut reopened = Journal::open(&path).unwrap();
+        let latest = reopened.latest().unwrap();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].status, EntryStatus::Succeeded);
+        assert_eq!(latest[0].deploy_hash.as_deref(), Some("ab12"));
+        assert_eq!(latest[1].status, EntryStatus::Pending);
+        assert_eq!(latest[1].deploy_hash, None);
+        assert_eq!(reopened.begin(serde_json::Value::Null).unwrap().id, 3);
+        assert_eq!(reopened.latest().unwrap().len(), 3);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
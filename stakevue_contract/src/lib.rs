@@ -1,7 +1,39 @@
 #![no_std]
 
+extern crate alloc;
+
+#[cfg(feature = "livenet")]
+extern crate std;
+
+pub mod amounts;
+
+pub mod planner;
+pub mod rebasing;
+
+#[cfg(feature = "livenet")]
+pub mod profile;
+
+#[cfg(feature = "livenet")]
+pub mod journal;
+
+#[cfg(feature = "livenet")]
+pub mod node;
+
+#[cfg(feature = "livenet")]
+pub mod submit;
+
+#[cfg(feature = "livenet")]
+pub mod telemetry;
+
+#[cfg(feature = "livenet")]
+pub mod alerts;
+
+#[cfg(feature = "livenet")]
+pub mod unbonding;
+
 use odra::prelude::*;
 use odra::casper_types::{U512, U256, PublicKey};
+use odra::casper_types::bytesrepr::{Bytes, ToBytes};
 use odra_modules::access::Ownable;
 use odra_modules::cep18_token::Cep18;
 
@@ -46,6 +78,55 @@ pub enum Error {
     ContractPaused = 19,
     RewardsTooHigh = 20,
     ValueOverflow = 21,
+    ValidatorNotFound = 22,
+    InvalidCommissionRate = 23,
+    ValidatorCapExceeded = 24,
+    ConcentrationLimitExceeded = 25,
+    InvalidBasisPoints = 26,
+    // admin_redelegate was withdrawn; the code stays reserved so later ones keep their numbers
+    SameValidator = 27,
+    DowngradeNotAllowed = 28,
+    LegacyImportAlreadySet = 29,
+    LegacyImportNotSet = 30,
+    InvalidMerkleProof = 31,
+    AlreadyMigrated = 32,
+    LegacyReserveExceeded = 33,
+    EmergencyModeActive = 34,
+    EmergencyModeNotActive = 35,
+    AdminStillActive = 36,
+    EmergencyAlreadyUndelegated = 37,
+    EmergencyNotUndelegated = 38,
+    TimelockEtaTooSoon = 39,
+    OperationNotFound = 40,
+    OperationNotQueued = 41,
+    OperationNotReady = 42,
+    OperationExpired = 43,
+    ApprovalRequired = 44,
+    NotApprover = 45,
+    ProposalNotFound = 46,
+    ProposalAlreadyExecuted = 47,
+    AlreadyApproved = 48,
+    ThresholdNotReached = 49,
+    InvalidThreshold = 50,
+    ApproverAlreadyExists = 51,
+    ApproverNotFound = 52,
+    TvlCapExceeded = 53,
+    AccountStakeLimitExceeded = 54,
+    BelowMinimumStake = 55,
+    NotAllowlisted = 56,
+    NotAllowlistManager = 57,
+    SelfReferral = 58,
+    NoReferralRewards = 59,
+    PermitExpired = 60,
+    InvalidSignature = 61,
+    MultisigDisabled = 62,
+    NoAccountSet = 63,
+    LegacyClaimWindowOpen = 64,
+    LegacyClaimWindowClosed = 65,
+    ReferrerMismatch = 66,
+    InsufficientAllowance = 67,
+    NotRebasingView = 68,
+    RebasingViewAlreadySet = 69,
 }
 
 // ============================================================================
@@ -60,6 +141,13 @@ pub struct Staked {
     pub stcspr_minted: U256,
 }
 
+#[odra::event]
+pub struct StakedFor {
+    pub payer: Address,
+    pub recipient: Address,
+    pub cspr_amount: U512,
+}
+
 #[odra::event]
 pub struct UnstakeRequested {
     pub staker: Address,
@@ -91,6 +179,16 @@ pub struct ValidatorRemoved {
     pub validator: PublicKey,
 }
 
+#[odra::event]
+pub struct ValidatorInfoUpdated {
+    pub validator: PublicKey,
+}
+
+#[odra::event]
+pub struct ConcentrationLimitUpdated {
+    pub max_validator_share_bps: u16,
+}
+
 #[odra::event]
 pub struct Delegated {
     pub validator: PublicKey,
@@ -120,6 +218,161 @@ pub struct LiquidityAdded {
     pub amount: U512,
 }
 
+#[odra::event]
+pub struct LegacyPositionsImported {
+    pub merkle_root: [u8; 32],
+    pub cspr_reserve: U512,
+}
+
+#[odra::event]
+pub struct MigratedStCsprClaimed {
+    pub account: Address,
+    pub cspr_amount: U512,
+    pub stcspr_minted: U256,
+}
+
+#[odra::event]
+pub struct LegacyReserveReleased {
+    pub recipient: Address,
+    pub cspr_amount: U512,
+}
+
+#[odra::event]
+pub struct EmergencyModeTriggered {
+    pub triggered_by: Address,
+    pub last_admin_activity: u64,
+}
+
+#[odra::event]
+pub struct EmergencyUndelegated {
+    pub staker: Address,
+    pub request_id: u64,
+    pub cspr_amount: U512,
+}
+
+#[odra::event]
+pub struct OperationQueued {
+    pub operation_id: u64,
+    pub kind: OperationKind,
+    pub eta: u64,
+}
+
+#[odra::event]
+pub struct OperationExecuted {
+    pub operation_id: u64,
+}
+
+#[odra::event]
+pub struct OperationCancelled {
+    pub operation_id: u64,
+}
+
+#[odra::event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub kind: ProposalKind,
+    pub proposer: Address,
+}
+
+#[odra::event]
+pub struct ProposalApproved {
+    pub proposal_id: u64,
+    pub approver: Address,
+    pub approvals: u8,
+}
+
+#[odra::event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+}
+
+#[odra::event]
+pub struct ApproverAdded {
+    pub approver: Address,
+}
+
+#[odra::event]
+pub struct ApproverRemoved {
+    pub approver: Address,
+}
+
+#[odra::event]
+pub struct ApprovalThresholdUpdated {
+    pub threshold: u8,
+}
+
+#[odra::event]
+pub struct DepositLimitsUpdated {
+    pub tvl_cap: U512,
+    pub max_stake_per_account: U512,
+    pub min_stake_amount: U512,
+}
+
+#[odra::event]
+pub struct AllowlistModeUpdated {
+    pub enabled: bool,
+}
+
+#[odra::event]
+pub struct AllowlistManagerUpdated {
+    pub manager: Address,
+}
+
+#[odra::event]
+pub struct AllowlistAdded {
+    pub account: Address,
+}
+
+#[odra::event]
+pub struct AllowlistRemoved {
+    pub account: Address,
+}
+
+#[odra::event]
+pub struct ReferralRecorded {
+    pub staker: Address,
+    pub referrer: Address,
+    pub cspr_amount: U512,
+}
+
+#[odra::event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Address,
+    pub stcspr_amount: U256,
+}
+
+#[odra::event]
+pub struct ProtocolFeeUpdated {
+    pub protocol_fee_bps: u16,
+    pub referral_fee_share_bps: u16,
+}
+
+#[odra::event]
+pub struct Approval {
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: U256,
+}
+
+#[odra::event]
+pub struct PermitApproval {
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: U256,
+    pub nonce: u64,
+}
+
+#[odra::event]
+pub struct RebasingViewUpdated {
+    pub view: Address,
+}
+
+#[odra::event]
+pub struct ContractMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
 // ============================================================================
 // WITHDRAWAL REQUEST
 // ============================================================================
@@ -175,6 +428,119 @@ impl odra::casper_types::CLTyped for WithdrawalRequest {
     }
 }
 
+// ============================================================================
+// VALIDATOR INFO
+// ============================================================================
+
+/// On-chain metadata for an approved validator, so bots and frontends
+/// can read names, commission and caps instead of hardcoding them
+#[odra::odra_type]
+pub struct ValidatorInfo {
+    /// Display name (e.g. "Make", "Arcadia")
+    pub name: String,
+    /// Commission snapshot in percent (0-100), as reported by the auction
+    pub commission_rate: u8,
+    /// Maximum CSPR the pool may delegate to this validator (0 = no cap)
+    pub max_allocation: U512,
+    /// Block time when the validator was added
+    pub added_at: u64,
+    /// Free-form operator notes
+    pub notes: String,
+}
+
+// ============================================================================
+// EXCHANGE RATE HISTORY
+// ============================================================================
+
+/// Exchange rate snapshot taken on harvest/sync
+#[odra::odra_type]
+pub struct RateSnapshot {
+    pub timestamp: u64,
+    /// Exchange rate with RATE_PRECISION decimals
+    pub rate: U512,
+    pub total_pool: U512,
+    pub total_supply: U256,
+}
+
+// ============================================================================
+// TIMELOCK
+// ============================================================================
+
+/// Sensitive admin operations that must wait TIMELOCK_DELAY before execution
+#[odra::odra_type]
+pub enum OperationKind {
+    RemoveValidator,
+    TransferOwnership,
+    SetEmergencyTimeout,
+    SetProtocolFee,
+    SetRebasingView,
+}
+
+#[odra::odra_type]
+pub enum OperationStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+/// Queued admin operation. Only the payload field matching `kind` is used.
+#[odra::odra_type]
+pub struct TimelockOperation {
+    pub kind: OperationKind,
+    pub validator: Option<PublicKey>,
+    pub account: Option<Address>,
+    pub value: u64,
+    /// Earliest block time the operation can be executed
+    pub eta: u64,
+    pub status: OperationStatus,
+}
+
+// ============================================================================
+// MULTISIG PROPOSALS
+// ============================================================================
+
+/// Admin actions that need M-of-N approver sign-off once a threshold is set
+#[odra::odra_type]
+pub enum ProposalKind {
+    AdminDelegate,
+    AdminUndelegate,
+    RemoveValidator,
+    TransferOwnership,
+    AddApprover,
+    RemoveApprover,
+    SetApprovalThreshold,
+    AdminProcessClaim,
+    AddValidator,
+    SetValidatorMaxAllocation,
+    SetMaxValidatorShare,
+    ExecuteOperation,
+    CancelOperation,
+    CreditPurseLiquidity,
+    ReleaseLegacyReserve,
+    SetEmergencyTimeout,
+    SetProtocolFee,
+    SetReferralFeeShare,
+    SetRebasingView,
+    SetAllowlistEnabled,
+    SetAllowlistManager,
+    SetTvlCap,
+    SetMaxStakePerAccount,
+    SetMinStakeAmount,
+}
+
+/// Proposed admin action. Only the payload fields matching `kind` are used.
+#[odra::odra_type]
+pub struct Proposal {
+    pub kind: ProposalKind,
+    pub proposer: Address,
+    pub validator: Option<PublicKey>,
+    pub account: Option<Address>,
+    pub amount: U512,
+    /// Approvals given, including approvers removed since (see approvals_from_current_approvers)
+    pub approvals: u8,
+    pub executed: bool,
+}
+
 // ============================================================================
 // STAKEVUE CONTRACT V20 - Wise Lending Architecture
 // ============================================================================
@@ -190,6 +556,25 @@ impl odra::casper_types::CLTyped for WithdrawalRequest {
 //   * This avoids the purse mismatch error (64658)
 // ============================================================================
 
+// Storage layout version written by init/migrate
+// Bump this and add a step to migrate_from() when adding state. Odra keys
+// storage by field position, so StakeVue fields are append-only: new
+// fields go at the end of the struct, existing ones are never moved,
+// removed or retyped.
+const CONTRACT_VERSION: u32 = 24;
+
+// Layout of upgradable installs from before contract_version existed (the
+// first 14 fields). V22 packages themselves were installed locked and can
+// only be replaced by a fresh deploy plus the legacy import.
+const LEGACY_CONTRACT_VERSION: u32 = 22;
+
+// Time legacy holders have to claim after the import (180 days in ms)
+const LEGACY_CLAIM_WINDOW: u64 = 180 * 24 * 60 * 60 * 1000;
+
+// Hash prefixes separating legacy snapshot leaves from internal merkle nodes
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
 // Precision for exchange rate calculations (9 decimals like CSPR)
 const RATE_PRECISION: u64 = 1_000_000_000;
 
@@ -203,7 +588,40 @@ const UNBONDING_BLOCKS: u64 = 5000;
 // Maximum number of validators
 const MAX_VALIDATORS: usize = 20;
 
-#[odra::module(events = [Staked, UnstakeRequested, Claimed, RewardsHarvested, ValidatorAdded, ValidatorRemoved, Delegated, Undelegated, AdminDelegated, AdminUndelegated, LiquidityAdded], errors = Error)]
+// Minimum delay between queueing and executing a timelocked operation (48h in ms)
+const TIMELOCK_DELAY: u64 = 48 * 60 * 60 * 1000;
+
+// Window after ETA during which a queued operation can still be executed (14 days in ms)
+const TIMELOCK_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60 * 1000;
+
+// Maximum protocol fee on harvested rewards (20%)
+const MAX_PROTOCOL_FEE_BPS: u16 = 2_000;
+
+// Precision for the referral rewards-per-TVL accumulator
+const ACC_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+// Domain tag for permit signatures (bump if the digest layout changes)
+const PERMIT_DOMAIN: &[u8] = b"StakeVue stCSPR permit v1";
+
+// Exchange rate snapshots kept on-chain (ring buffer size)
+const RATE_HISTORY_SIZE: u64 = 256;
+
+// Max snapshots returned by one get_rate_history call
+const MAX_RATE_HISTORY_PAGE: u64 = 50;
+
+// Milliseconds per year, for APR estimates
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+// Admin inactivity before anyone can trigger emergency mode (30 days in ms)
+const DEFAULT_EMERGENCY_TIMEOUT: u64 = 30 * 24 * 60 * 60 * 1000;
+
+// Maximum validator commission (percent)
+const MAX_COMMISSION_RATE: u8 = 100;
+
+// Basis points denominator (10_000 = 100%)
+const BPS_DENOMINATOR: u16 = 10_000;
+
+#[odra::module(events = [Staked, StakedFor, UnstakeRequested, Claimed, RewardsHarvested, ValidatorAdded, ValidatorRemoved, ValidatorInfoUpdated, ConcentrationLimitUpdated, Delegated, Undelegated, AdminDelegated, AdminUndelegated, LiquidityAdded, LegacyPositionsImported, MigratedStCsprClaimed, LegacyReserveReleased, EmergencyModeTriggered, EmergencyUndelegated, OperationQueued, OperationExecuted, OperationCancelled, ProposalCreated, ProposalApproved, ProposalExecuted, ApproverAdded, ApproverRemoved, ApprovalThresholdUpdated, DepositLimitsUpdated, AllowlistModeUpdated, AllowlistManagerUpdated, AllowlistAdded, AllowlistRemoved, ReferralRecorded, ReferralRewardsClaimed, ProtocolFeeUpdated, Approval, PermitApproval, RebasingViewUpdated, ContractMigrated], errors = Error)]
 pub struct StakeVue {
     /// Access control
     ownable: SubModule<Ownable>,
@@ -233,6 +651,94 @@ pub struct StakeVue {
     user_requests: Mapping<(Address, u64), u64>,
     /// User's request count
     user_request_count: Mapping<Address, u64>,
+    /// Payout address when different from the staker (request_id -> receiver)
+    withdrawal_receiver: Mapping<u64, Address>,
+    /// Storage layout version (see CONTRACT_VERSION)
+    contract_version: Var<u32>,
+    /// Merkle root of the legacy deployment snapshot (account, CSPR value)
+    legacy_merkle_root: Var<[u8; 32]>,
+    /// CSPR imported for legacy holders that has not been claimed yet
+    legacy_reserve: Var<U512>,
+    /// Legacy holders who already claimed their migrated stCSPR
+    legacy_claimed: Mapping<Address, bool>,
+    /// Block time of the last owner action (dead-man's switch)
+    last_admin_activity: Var<u64>,
+    /// Inactivity period after which emergency mode can be triggered
+    emergency_timeout: Var<u64>,
+    /// Emergency mode: users undelegate and claim without the owner
+    emergency_mode: Var<bool>,
+    /// Block time a request was emergency-undelegated (request_id -> time)
+    emergency_undelegated_at: Mapping<u64, u64>,
+    /// Timelocked admin operations (id -> operation)
+    timelock_operations: Mapping<u64, TimelockOperation>,
+    /// Next timelock operation ID
+    next_operation_id: Var<u64>,
+    /// Registered multisig approvers
+    approvers: Mapping<Address, bool>,
+    /// Number of registered approvers
+    approver_count: Var<u8>,
+    /// Approvals required per proposal (0 = multisig disabled, owner acts alone)
+    approval_threshold: Var<u8>,
+    /// Admin action proposals (id -> proposal)
+    proposals: Mapping<u64, Proposal>,
+    /// Approvals given (proposal_id, approver) -> approved
+    proposal_approvals: Mapping<(u64, Address), bool>,
+    /// Next proposal ID
+    next_proposal_id: Var<u64>,
+    /// Max total_cspr_pool accepted by stake (0 = no cap)
+    tvl_cap: Var<U512>,
+    /// Max CSPR value a single account may hold through stake (0 = no limit)
+    max_stake_per_account: Var<U512>,
+    /// Minimum CSPR per stake call (0 = any amount)
+    min_stake_amount: Var<U512>,
+    /// Permissioned mode: only allowlisted accounts may stake or move stCSPR
+    allowlist_enabled: Var<bool>,
+    /// Account allowed to edit the allowlist besides the owner
+    allowlist_manager: Var<Address>,
+    /// Allowlisted accounts
+    allowlisted: Mapping<Address, bool>,
+    /// Fee on harvested rewards, minted as stCSPR (bps, changed via timelock)
+    protocol_fee_bps: Var<u16>,
+    /// Referrer of each staker (every referred deposit must name the same one)
+    staker_referrer: Mapping<Address, Address>,
+    /// Staker's CSPR principal still attributed to their referrer
+    referred_principal: Mapping<Address, U512>,
+    /// Active referred TVL per referrer
+    referred_tvl: Mapping<Address, U512>,
+    /// Number of stakers a referrer brought in
+    referral_count: Mapping<Address, u64>,
+    /// Sum of referred_tvl over all referrers
+    total_referred_tvl: Var<U512>,
+    /// Referral stCSPR per unit of referred TVL (scaled by ACC_PRECISION)
+    referral_acc_per_tvl: Var<U512>,
+    /// Accumulator value at each referrer's last settlement
+    referral_checkpoint: Mapping<Address, U512>,
+    /// Settled, unclaimed referral stCSPR per referrer
+    referral_rewards: Mapping<Address, U256>,
+    /// stCSPR allowances (owner, spender) -> amount, set by approve() and permit()
+    allowances: Mapping<(Address, Address), U256>,
+    /// Next permit nonce per owner
+    permit_nonces: Mapping<Address, u64>,
+    /// Exchange rate ring buffer (slot -> snapshot), slot = index % RATE_HISTORY_SIZE
+    rate_snapshots: Mapping<u64, RateSnapshot>,
+    /// Number of snapshots ever recorded
+    rate_snapshot_count: Var<u64>,
+    /// CSPR actually undelegated for an emergency-undelegated request (request_id -> amount)
+    emergency_undelegated: Mapping<u64, U512>,
+    /// Emergency-undelegated CSPR not claimed yet (held in the purse outside available_liquidity)
+    emergency_unbonding: Var<U512>,
+    /// Who gave each approval (proposal_id, approval index) -> approver
+    proposal_approver_at: Mapping<(u64, u8), Address>,
+    /// Block time of import_legacy_positions (starts the claim window)
+    legacy_imported_at: Var<u64>,
+    /// RebasingStCspr contract allowed to move shares for its callers
+    rebasing_view: Var<Address>,
+    /// Validator metadata (name, commission, cap, notes)
+    validator_info: Mapping<PublicKey, ValidatorInfo>,
+    /// Max share of total_cspr_pool a single validator may hold (bps, 0 = no limit)
+    max_validator_share_bps: Var<u16>,
+    /// Share of the protocol fee on referred principal's rewards paid to referrers (bps of the fee)
+    referral_fee_share_bps: Var<u16>,
 }
 
 #[odra::module]
@@ -246,6 +752,12 @@ impl StakeVue {
         self.pending_undelegations.set(U512::zero());
         self.validator_count.set(0);
         self.next_request_id.set(1);
+        self.next_operation_id.set(1);
+        self.next_proposal_id.set(1);
+        self.contract_version.set(CONTRACT_VERSION);
+        self.last_admin_activity.set(self.env().get_block_time());
+        self.emergency_timeout.set(DEFAULT_EMERGENCY_TIMEOUT);
+        self.emergency_mode.set(false);
 
         // Initialize the integrated stCSPR token
         self.token.init(
@@ -256,6 +768,68 @@ impl StakeVue {
         );
     }
 
+    /// Upgrade hook, called by Odra when a new version is installed
+    /// into the existing package. State is kept, only migrations run.
+    /// Like migrate(), only the owner may run it.
+    pub fn upgrade(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.run_migrations();
+    }
+
+    /// Run pending storage migrations (owner only)
+    ///
+    /// Normally triggered by upgrade(); exposed so a failed or skipped
+    /// migration can be retried. No-op when already at CONTRACT_VERSION.
+    pub fn migrate(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.run_migrations();
+    }
+
+    fn run_migrations(&mut self) {
+        let from_version = self.contract_version.get().unwrap_or(LEGACY_CONTRACT_VERSION);
+        self.migrate_from(from_version);
+    }
+
+    fn migrate_from(&mut self, from_version: u32) {
+        if from_version > CONTRACT_VERSION {
+            self.env().revert(Error::DowngradeNotAllowed);
+        }
+        if from_version == CONTRACT_VERSION {
+            return;
+        }
+
+        // Pre-versioning layout -> V24: backfill metadata for validators added before the registry,
+        // start the emergency dead-man's switch from now
+        if from_version < 24 {
+            let now = self.env().get_block_time();
+            self.last_admin_activity.set(now);
+            self.emergency_timeout.set(DEFAULT_EMERGENCY_TIMEOUT);
+            self.emergency_mode.set(false);
+            self.next_operation_id.set(1);
+            self.next_proposal_id.set(1);
+
+            for index in 0..self.validator_count.get_or_default() {
+                if let Some(validator) = self.validators.get(&index) {
+                    if self.validator_info.get(&validator).is_none() {
+                        self.validator_info.set(&validator, ValidatorInfo {
+                            name: String::new(),
+                            commission_rate: 0,
+                            max_allocation: U512::zero(),
+                            added_at: now,
+                            notes: String::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        self.contract_version.set(CONTRACT_VERSION);
+        self.env().emit_event(ContractMigrated {
+            from_version,
+            to_version: CONTRACT_VERSION,
+        });
+    }
+
     // ========================================================================
     // STAKING FUNCTIONS (V20 - Pool Based)
     // ========================================================================
@@ -268,6 +842,37 @@ impl StakeVue {
     #[odra(payable)]
     pub fn stake(&mut self, validator: PublicKey) {
         let staker = self.env().caller();
+        self.stake_internal(staker, validator, None);
+    }
+
+    /// Stake CSPR and mint the stCSPR to `recipient` instead of the caller
+    ///
+    /// For custodians and the proxy-caller WASM flow. Allowlist and
+    /// deposit limits are checked against the recipient.
+    #[odra(payable)]
+    pub fn stake_for(&mut self, recipient: Address, validator: PublicKey) {
+        self.stake_internal(recipient, validator, None);
+        self.env().emit_event(StakedFor {
+            payer: self.env().caller(),
+            recipient,
+            cspr_amount: self.env().attached_value(),
+        });
+    }
+
+    /// Stake CSPR crediting a referrer
+    ///
+    /// The deposit counts towards the referrer's TVL until unstaked. A
+    /// staker has one referrer at a time: a deposit naming another one is
+    /// rejected while referred principal is still attributed.
+    #[odra(payable)]
+    pub fn stake_with_referral(&mut self, validator: PublicKey, referrer: Address) {
+        let staker = self.env().caller();
+        self.stake_internal(staker, validator, Some(referrer));
+    }
+
+    fn stake_internal(&mut self, staker: Address, validator: PublicKey, referrer: Option<Address>) {
+        self.assert_not_emergency();
+
         let cspr_amount = self.env().attached_value();
 
         if cspr_amount == U512::zero() {
@@ -279,6 +884,10 @@ impl StakeVue {
             self.env().revert(Error::ValidatorNotApproved);
         }
 
+        // Guarded launch limits
+        self.assert_allowlisted(&staker);
+        self.assert_deposit_limits(&staker, cspr_amount);
+
         // Calculate stCSPR to mint based on exchange rate
         let stcspr_to_mint = self.cspr_to_stcspr(cspr_amount);
 
@@ -297,6 +906,10 @@ impl StakeVue {
         // Admin will call admin_delegate() to delegate pool funds to validators
         // This avoids the purse mismatch error (64658)
 
+        if let Some(referrer) = referrer {
+            self.record_referral(staker, referrer, cspr_amount);
+        }
+
         self.env().emit_event(Staked {
             staker,
             validator,
@@ -313,7 +926,23 @@ impl StakeVue {
     /// User can claim when liquidity is available in the pool.
     pub fn request_unstake(&mut self, stcspr_amount: U512) -> u64 {
         let staker = self.env().caller();
+        self.request_unstake_internal(staker, stcspr_amount)
+    }
+
+    /// Request unstake with the CSPR paid to `receiver` on claim
+    ///
+    /// The caller burns their stCSPR and keeps ownership of the request
+    /// (only they can claim it), but the CSPR goes to `receiver`.
+    pub fn request_unstake_for(&mut self, stcspr_amount: U512, receiver: Address) -> u64 {
+        let staker = self.env().caller();
+        let request_id = self.request_unstake_internal(staker, stcspr_amount);
+        if receiver != staker {
+            self.withdrawal_receiver.set(&request_id, receiver);
+        }
+        request_id
+    }
 
+    fn request_unstake_internal(&mut self, staker: Address, stcspr_amount: U512) -> u64 {
         // Convert U512 to U256 for internal use (stCSPR is U256 token)
         let stcspr_amount_u256 = u512_to_u256(stcspr_amount);
 
@@ -342,6 +971,9 @@ impl StakeVue {
         // Update total pool
         self.total_cspr_pool.set(pool - cspr_to_return);
 
+        // Withdrawn principal no longer counts for the staker's referrer
+        self.release_referral(staker, cspr_to_return);
+
         // Add to pending withdrawals
         let pending = self.pending_withdrawals.get_or_default();
         self.pending_withdrawals.set(pending + cspr_to_return);
@@ -429,8 +1061,9 @@ impl StakeVue {
         // Reduce available liquidity
         self.available_liquidity.set(liquidity - request.cspr_amount);
 
-        // Transfer CSPR to staker from pool
-        self.env().transfer_tokens(&caller, &request.cspr_amount);
+        // Transfer CSPR to staker (or chosen receiver) from pool
+        let receiver = self.get_withdrawal_receiver(request_id).unwrap_or(caller);
+        self.env().transfer_tokens(&receiver, &request.cspr_amount);
 
         self.env().emit_event(Claimed {
             staker: caller,
@@ -444,8 +1077,11 @@ impl StakeVue {
     /// V20+: Admin/bot can process ready claims on behalf of users.
     /// CSPR is sent directly to the original staker, not the caller.
     pub fn admin_process_claim(&mut self, request_id: u64) {
-        self.ownable.assert_owner(&self.env().caller());
+        self.assert_owner_acting_alone();
+        self.process_claim(request_id);
+    }
 
+    fn process_claim(&mut self, request_id: u64) {
         // Get withdrawal request
         let request = self.withdrawal_requests.get(&request_id);
         if request.is_none() {
@@ -481,8 +1117,9 @@ impl StakeVue {
         // Reduce available liquidity
         self.available_liquidity.set(liquidity - request.cspr_amount);
 
-        // Transfer CSPR to original STAKER (not caller!)
-        self.env().transfer_tokens(&request.staker, &request.cspr_amount);
+        // Transfer CSPR to original STAKER or their receiver (not caller!)
+        let receiver = self.get_withdrawal_receiver(request_id).unwrap_or(request.staker);
+        self.env().transfer_tokens(&receiver, &request.cspr_amount);
 
         self.env().emit_event(Claimed {
             staker: request.staker,
@@ -497,604 +1134,3421 @@ impl StakeVue {
     }
 
     // ========================================================================
-    // EXCHANGE RATE FUNCTIONS
+    // LEGACY MIGRATION
     // ========================================================================
 
-    /// Get current exchange rate (with 9 decimal precision)
-    pub fn get_exchange_rate(&self) -> U512 {
-        let total_cspr = self.total_cspr_pool.get_or_default();
-        let total_stcspr = self.token.total_supply();
+    /// Import a snapshot of legacy deployment positions (owner only, once)
+    ///
+    /// The snapshot is a merkle tree of (account, CSPR value) leaves, see
+    /// get_legacy_leaf(). The attached CSPR backs the snapshot and is held
+    /// aside until holders claim, so the exchange rate is unaffected.
+    /// Carries CSPR, so it has no proposal kind: import before setting an
+    /// approval threshold.
+    #[odra(payable)]
+    pub fn import_legacy_positions(&mut self, merkle_root: [u8; 32]) {
+        self.assert_owner_acting_alone();
 
-        if total_stcspr == U256::zero() {
-            return U512::from(RATE_PRECISION);
+        if self.legacy_merkle_root.get().is_some() {
+            self.env().revert(Error::LegacyImportAlreadySet);
         }
 
-        let precision = U512::from(RATE_PRECISION);
-        let total_stcspr_512 = u256_to_u512(total_stcspr);
-
-        (total_cspr * precision) / total_stcspr_512
-    }
-
-    fn cspr_to_stcspr(&self, cspr_amount: U512) -> U256 {
-        let total_cspr = self.total_cspr_pool.get_or_default();
-        let total_stcspr = self.token.total_supply();
-
-        if total_stcspr == U256::zero() || total_cspr == U512::zero() {
-            return u512_to_u256(cspr_amount);
+        let cspr_reserve = self.env().attached_value();
+        if cspr_reserve == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
         }
 
-        let total_stcspr_512 = u256_to_u512(total_stcspr);
-        let result = (cspr_amount * total_stcspr_512) / total_cspr;
-        u512_to_u256(result)
+        self.legacy_merkle_root.set(merkle_root);
+        self.legacy_reserve.set(cspr_reserve);
+        self.legacy_imported_at.set(self.env().get_block_time());
+
+        self.env().emit_event(LegacyPositionsImported {
+            merkle_root,
+            cspr_reserve,
+        });
     }
 
-    fn stcspr_to_cspr(&self, stcspr_amount: U256) -> U512 {
-        let total_cspr = self.total_cspr_pool.get_or_default();
-        let total_stcspr = self.token.total_supply();
+    /// Claim stCSPR for a legacy position
+    ///
+    /// Caller proves their (account, cspr_amount) leaf is in the imported
+    /// snapshot. stCSPR is minted at the current exchange rate, exactly as
+    /// if cspr_amount had been staked, and the CSPR joins the pool.
+    pub fn claim_migrated_stcspr(&mut self, cspr_amount: U512, proof: Vec<[u8; 32]>) -> U256 {
+        let account = self.env().caller();
+        self.assert_allowlisted(&account);
+
+        let root = match self.legacy_merkle_root.get() {
+            Some(root) => root,
+            None => self.env().revert(Error::LegacyImportNotSet),
+        };
 
-        if total_stcspr == U256::zero() {
-            return U512::zero();
+        if self.env().get_block_time() > self.get_legacy_claim_deadline() {
+            self.env().revert(Error::LegacyClaimWindowClosed);
         }
 
-        let stcspr_512 = u256_to_u512(stcspr_amount);
-        let total_stcspr_512 = u256_to_u512(total_stcspr);
-
-        (stcspr_512 * total_cspr) / total_stcspr_512
-    }
-
-    // ========================================================================
-    // ADMIN FUNCTIONS (V20 - Pool Management)
-    // ========================================================================
-
-    /// Delegate pool funds to a validator (owner only)
-    ///
-    /// V20: Admin controls when to delegate. Takes CSPR from available_liquidity
-    /// and delegates to the specified validator.
-    pub fn admin_delegate(&mut self, validator: PublicKey, amount: U512) {
-        self.ownable.assert_owner(&self.env().caller());
+        if self.legacy_claimed.get(&account).unwrap_or(false) {
+            self.env().revert(Error::AlreadyMigrated);
+        }
 
-        if amount == U512::zero() {
+        if cspr_amount == U512::zero() {
             self.env().revert(Error::ZeroAmount);
         }
 
-        // Check validator is approved
-        if !self.validator_active.get(&validator).unwrap_or(false) {
-            self.env().revert(Error::ValidatorNotApproved);
+        // Verify leaf against the snapshot root
+        let mut node = self.get_legacy_leaf(account, cspr_amount);
+        for sibling in proof.iter() {
+            node = self.hash_pair(node, *sibling);
+        }
+        if node != root {
+            self.env().revert(Error::InvalidMerkleProof);
         }
 
-        // Check we have enough liquidity to delegate
-        let liquidity = self.available_liquidity.get_or_default();
-        if amount > liquidity {
-            self.env().revert(Error::InsufficientLiquidity);
+        let reserve = self.legacy_reserve.get_or_default();
+        if cspr_amount > reserve {
+            self.env().revert(Error::LegacyReserveExceeded);
         }
 
-        // Check minimum delegation requirement
-        let current_delegated = self.validator_delegated.get(&validator).unwrap_or(U512::zero());
-        if current_delegated == U512::zero() && amount < U512::from(MIN_DELEGATION) {
-            self.env().revert(Error::BelowMinimumDelegation);
-        }
+        self.legacy_claimed.set(&account, true);
+        self.legacy_reserve.set(reserve - cspr_amount);
 
-        // Reduce available liquidity
-        self.available_liquidity.set(liquidity - amount);
+        // Same accounting as stake()
+        let stcspr_minted = self.cspr_to_stcspr(cspr_amount);
+        self.token.raw_mint(&account, &stcspr_minted);
 
-        // Update validator delegated amount
-        self.validator_delegated.set(&validator, current_delegated + amount);
+        let pool = self.total_cspr_pool.get_or_default();
+        self.total_cspr_pool.set(pool + cspr_amount);
 
-        // Actually delegate to auction contract
-        #[cfg(not(test))]
-        {
-            self.env().delegate(validator.clone(), amount);
-        }
+        let liquidity = self.available_liquidity.get_or_default();
+        self.available_liquidity.set(liquidity + cspr_amount);
 
-        self.env().emit_event(AdminDelegated {
-            validator,
-            amount,
+        self.env().emit_event(MigratedStCsprClaimed {
+            account,
+            cspr_amount,
+            stcspr_minted,
         });
-    }
 
-    /// Undelegate from a validator (owner only)
-    ///
-    /// V20: Admin calls this to undelegate. After unbonding period,
-    /// call admin_add_liquidity() to add the returned CSPR to the pool.
-    pub fn admin_undelegate(&mut self, validator: PublicKey, amount: U512) {
-        self.ownable.assert_owner(&self.env().caller());
+        stcspr_minted
+    }
 
-        if amount == U512::zero() {
-            self.env().revert(Error::ZeroAmount);
-        }
+    /// Return CSPR nobody claimed to the owner once the claim window closed
+    /// (owner only). Claims are rejected from then on.
+    pub fn release_legacy_reserve(&mut self) -> U512 {
+        self.assert_owner_acting_alone();
+        self.release_legacy_reserve_to(self.ownable.get_owner())
+    }
 
-        // Check validator has enough delegated
-        let delegated = self.validator_delegated.get(&validator).unwrap_or(U512::zero());
-        if amount > delegated {
-            self.env().revert(Error::UndelegateAmountExceedsDelegation);
+    fn release_legacy_reserve_to(&mut self, recipient: Address) -> U512 {
+        if self.legacy_merkle_root.get().is_none() {
+            self.env().revert(Error::LegacyImportNotSet);
         }
-
-        // Update validator delegated amount
-        self.validator_delegated.set(&validator, delegated - amount);
-
-        // Reduce pending undelegations (this amount is now being processed)
-        let pending_undel = self.pending_undelegations.get_or_default();
-        if amount <= pending_undel {
-            self.pending_undelegations.set(pending_undel - amount);
-        } else {
-            self.pending_undelegations.set(U512::zero());
+        if self.env().get_block_time() <= self.get_legacy_claim_deadline() {
+            self.env().revert(Error::LegacyClaimWindowOpen);
         }
 
-        // Actually undelegate from auction contract
-        #[cfg(not(test))]
-        {
-            self.env().undelegate(validator.clone(), amount);
+        let cspr_amount = self.legacy_reserve.get_or_default();
+        if cspr_amount == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
         }
+        self.legacy_reserve.set(U512::zero());
+        self.env().transfer_tokens(&recipient, &cspr_amount);
 
-        self.env().emit_event(AdminUndelegated {
-            validator,
-            amount,
+        self.env().emit_event(LegacyReserveReleased {
+            recipient,
+            cspr_amount,
         });
+        cspr_amount
     }
 
-    /// Add liquidity to the pool (owner only)
-    ///
-    /// V20: After unbonding completes, admin calls this to add the
-    /// returned CSPR back to available_liquidity for claims.
-    #[odra(payable)]
-    pub fn admin_add_liquidity(&mut self) {
-        self.ownable.assert_owner(&self.env().caller());
+    /// Get the snapshot leaf hash for a legacy position
+    /// Off-chain tooling uses this to build the same tree the contract checks:
+    /// leaves are hash(0x00 || account || amount), nodes hash(0x01 || sorted pair)
+    pub fn get_legacy_leaf(&self, account: Address, cspr_amount: U512) -> [u8; 32] {
+        let mut data = [MERKLE_LEAF_PREFIX].to_vec();
+        data.append(&mut account.to_bytes().unwrap_or_default());
+        data.append(&mut cspr_amount.to_bytes().unwrap_or_default());
+        self.env().hash(data)
+    }
 
-        let amount = self.env().attached_value();
-        if amount == U512::zero() {
-            self.env().revert(Error::ZeroAmount);
+    /// Get last block time legacy positions can be claimed (0 before the import)
+    pub fn get_legacy_claim_deadline(&self) -> u64 {
+        match self.legacy_imported_at.get() {
+            Some(imported_at) => imported_at.saturating_add(LEGACY_CLAIM_WINDOW),
+            None => 0,
         }
+    }
 
-        // Add to available liquidity
-        let liquidity = self.available_liquidity.get_or_default();
-        self.available_liquidity.set(liquidity + amount);
+    /// Get imported snapshot root (None until import_legacy_positions)
+    pub fn get_legacy_merkle_root(&self) -> Option<[u8; 32]> {
+        self.legacy_merkle_root.get()
+    }
 
-        self.env().emit_event(LiquidityAdded { amount });
+    /// Get CSPR still reserved for unclaimed legacy positions
+    pub fn get_legacy_reserve(&self) -> U512 {
+        self.legacy_reserve.get_or_default()
     }
 
-    /// Add a validator to the approved list (owner only)
-    pub fn add_validator(&mut self, validator: PublicKey) {
-        self.ownable.assert_owner(&self.env().caller());
+    /// Check if an account already claimed its legacy position
+    pub fn is_legacy_claimed(&self, account: Address) -> bool {
+        self.legacy_claimed.get(&account).unwrap_or(false)
+    }
 
-        // Check not already active
-        if self.validator_active.get(&validator).unwrap_or(false) {
-            self.env().revert(Error::ValidatorAlreadyExists);
+    /// Hash two merkle nodes in sorted order, so proofs need no position bits
+    fn hash_pair(&self, a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let mut data = Vec::with_capacity(65);
+        data.push(MERKLE_NODE_PREFIX);
+        if a <= b {
+            data.extend_from_slice(&a);
+            data.extend_from_slice(&b);
+        } else {
+            data.extend_from_slice(&b);
+            data.extend_from_slice(&a);
         }
+        self.env().hash(data)
+    }
 
-        // Check max validators
-        let count = self.validator_count.get_or_default();
-        if count as usize >= MAX_VALIDATORS {
-            self.env().revert(Error::MaxValidatorsReached);
-        }
+    // ========================================================================
+    // stCSPR TOKEN (CEP-18)
+    // ========================================================================
 
-        // Add validator
-        self.validators.set(&count, validator.clone());
-        self.validator_active.set(&validator, true);
-        self.validator_delegated.set(&validator, U512::zero());
-        self.validator_count.set(count + 1);
+    /// Transfer stCSPR to another account
+    pub fn transfer(&mut self, recipient: Address, amount: U256) {
+        let owner = self.env().caller();
+        self.assert_allowlisted(&owner);
+        self.assert_allowlisted(&recipient);
+        if recipient != owner {
+            self.release_referral_for_shares(owner, amount);
+        }
+        self.token.transfer(&recipient, &amount);
+    }
 
-        self.env().emit_event(ValidatorAdded { validator });
+    /// Transfer stCSPR on behalf of `owner` using an allowance
+    pub fn transfer_from(&mut self, owner: Address, recipient: Address, amount: U256) {
+        let spender = self.env().caller();
+        self.assert_allowlisted(&spender);
+        self.spend_allowance(owner, spender, amount);
+        self.move_shares(owner, recipient, amount);
     }
 
-    /// Remove a validator from the approved list (owner only)
-    /// Note: Does not affect existing delegations
-    pub fn remove_validator(&mut self, validator: PublicKey) {
-        self.ownable.assert_owner(&self.env().caller());
-        self.validator_active.set(&validator, false);
-        self.env().emit_event(ValidatorRemoved { validator });
+    /// Approve `spender` to transfer caller's stCSPR
+    /// Sets (not adds to) the allowance, replacing any earlier permit
+    pub fn approve(&mut self, spender: Address, amount: U256) {
+        self.assert_allowlisted(&spender);
+        let owner = self.env().caller();
+        self.allowances.set(&(owner, spender), amount);
+        self.env().emit_event(Approval { owner, spender, amount });
     }
 
-    /// Harvest rewards and add to pool (owner only)
-    /// This increases the exchange rate
-    /// Limited to 10% of pool per call to prevent manipulation
-    #[odra(payable)]
-    pub fn harvest_rewards(&mut self) {
-        self.ownable.assert_owner(&self.env().caller());
+    /// Get stCSPR allowance, from approve() or permit() whichever came last
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.get(&(owner, spender)).unwrap_or_default()
+    }
 
-        let reward_amount = self.env().attached_value();
-        if reward_amount == U512::zero() {
-            self.env().revert(Error::ZeroAmount);
+    /// Approve `spender` with a signature from `owner` instead of a deploy
+    ///
+    /// `signature` is the owner key's signature over get_permit_digest()
+    /// for the owner's current nonce. Sets (not adds to) the same allowance
+    /// as approve(), so a DEX router can permit and pull in one deploy and
+    /// the owner can revoke it with approve(spender, 0).
+    pub fn permit(
+        &mut self,
+        owner: PublicKey,
+        spender: Address,
+        amount: U256,
+        deadline: u64,
+        signature: Bytes,
+    ) {
+        if self.env().get_block_time() > deadline {
+            self.env().revert(Error::PermitExpired);
         }
+        self.assert_allowlisted(&spender);
 
-        let pool = self.total_cspr_pool.get_or_default();
+        let owner_address = Address::from(owner.to_account_hash());
+        let nonce = self.permit_nonces.get(&owner_address).unwrap_or(0);
+        let digest = self.get_permit_digest(owner_address, spender, amount, nonce, deadline);
 
-        // Security: limit rewards to 10% of current pool to prevent manipulation
-        let max_reward = pool / U512::from(10); // 10% max
-        if reward_amount > max_reward && pool > U512::zero() {
-            self.env().revert(Error::RewardsTooHigh);
+        let message = Bytes::from(digest.to_vec());
+        if !self.env().verify_signature(&message, &signature, &owner) {
+            self.env().revert(Error::InvalidSignature);
         }
 
-        self.total_cspr_pool.set(pool + reward_amount);
+        self.permit_nonces.set(&owner_address, nonce + 1);
+        self.allowances.set(&(owner_address, spender), amount);
 
-        let new_rate = self.get_exchange_rate();
-        self.env().emit_event(RewardsHarvested {
-            amount: reward_amount,
-            new_exchange_rate: new_rate,
+        self.env().emit_event(PermitApproval {
+            owner: owner_address,
+            spender,
+            amount,
+            nonce,
         });
     }
 
-    /// Transfer ownership (owner only)
-    pub fn transfer_ownership(&mut self, new_owner: Address) {
-        self.ownable.transfer_ownership(&new_owner);
+    /// Get the digest an owner signs for permit()
+    /// Binds the domain tag and this contract so signatures can't be replayed elsewhere
+    pub fn get_permit_digest(
+        &self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+        nonce: u64,
+        deadline: u64,
+    ) -> [u8; 32] {
+        let mut data = PERMIT_DOMAIN.to_vec();
+        data.append(&mut self.env().self_address().to_bytes().unwrap_or_default());
+        data.append(&mut owner.to_bytes().unwrap_or_default());
+        data.append(&mut spender.to_bytes().unwrap_or_default());
+        data.append(&mut amount.to_bytes().unwrap_or_default());
+        data.append(&mut nonce.to_bytes().unwrap_or_default());
+        data.append(&mut deadline.to_bytes().unwrap_or_default());
+        self.env().hash(data)
+    }
+
+    /// Get next permit nonce for an owner
+    pub fn get_permit_nonce(&self, owner: Address) -> u64 {
+        self.permit_nonces.get(&owner).unwrap_or(0)
     }
 
     // ========================================================================
-    // VIEW FUNCTIONS
+    // REBASING stCSPR VIEW
     // ========================================================================
+    // The CSPR-denominated view lives in its own contract (see rebasing.rs).
+    // It is registered once, through the timelock, and moves shares through
+    // rebasing_view_transfer(), which always spends the share allowance of
+    // approve() and permit(): the view vouches only for who called it.
+
+    /// Queue registering the RebasingStCspr contract (owner only),
+    /// executable from `eta`. The view can be set only once.
+    pub fn queue_set_rebasing_view(&mut self, view: Address, eta: u64) -> u64 {
+        self.assert_owner_acting_alone();
+        self.queue_rebasing_view(view, eta)
+    }
 
-    /// Get stCSPR balance of an address
-    pub fn get_stcspr_balance(&self, account: Address) -> U256 {
-        self.token.balance_of(&account)
+    fn queue_rebasing_view(&mut self, view: Address, eta: u64) -> u64 {
+        self.assert_rebasing_view_unset();
+        if !view.is_contract() {
+            self.env().revert(Error::NotRebasingView);
+        }
+        self.queue_operation(OperationKind::SetRebasingView, None, Some(view), 0, eta)
     }
 
-    /// Get CSPR value of stCSPR balance
-    pub fn get_cspr_value(&self, account: Address) -> U512 {
-        let stcspr_balance = self.token.balance_of(&account);
-        self.stcspr_to_cspr(stcspr_balance)
+    /// Register the RebasingStCspr contract (via timelock)
+    fn set_rebasing_view(&mut self, view: Address) {
+        self.assert_rebasing_view_unset();
+        self.rebasing_view.set(view);
+        self.env().emit_event(RebasingViewUpdated { view });
     }
 
-    /// Get total CSPR in pool
-    pub fn get_total_pool(&self) -> U512 {
-        self.total_cspr_pool.get_or_default()
+    fn assert_rebasing_view_unset(&self) {
+        if self.rebasing_view.get().is_some() {
+            self.env().revert(Error::RebasingViewAlreadySet);
+        }
     }
 
-    /// Get total pending withdrawals
-    pub fn get_pending_withdrawals(&self) -> U512 {
-        self.pending_withdrawals.get_or_default()
+    /// Move `owner`'s shares for `spender`, the caller of the rebasing view
+    /// (view only). Spends `spender`'s allowance like transfer_from()
+    pub fn rebasing_view_transfer(&mut self, owner: Address, spender: Address, recipient: Address, shares: U256) {
+        if self.rebasing_view.get() != Some(self.env().caller()) {
+            self.env().revert(Error::NotRebasingView);
+        }
+        self.assert_allowlisted(&spender);
+        self.spend_allowance(owner, spender, shares);
+        self.move_shares(owner, recipient, shares);
     }
 
-    /// Get available liquidity (V20)
-    /// This is CSPR in the contract that can be used for claims
-    pub fn get_available_liquidity(&self) -> U512 {
-        self.available_liquidity.get_or_default()
+    /// Get the RebasingStCspr contract, if set
+    pub fn get_rebasing_view(&self) -> Option<Address> {
+        self.rebasing_view.get()
     }
 
-    /// Get pending undelegations (V20)
-    /// Amount that needs to be undelegated by admin
-    pub fn get_pending_undelegations(&self) -> U512 {
-        self.pending_undelegations.get_or_default()
+    // ========================================================================
+    // ALLOWLIST (permissioned pool mode)
+    // ========================================================================
+    // When enabled, every way stCSPR reaches or leaves an account checks
+    // it: stake, migrated and referral claims, transfers, and spenders in
+    // approve/permit/transfer_from. request_unstake stays open as the exit.
+
+    /// Enable or disable allowlist mode (owner only)
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) {
+        self.assert_owner_acting_alone();
+        self.update_allowlist_enabled(enabled);
     }
 
-    /// Get contract owner
-    pub fn get_owner(&self) -> Address {
-        self.ownable.get_owner()
+    fn update_allowlist_enabled(&mut self, enabled: bool) {
+        self.allowlist_enabled.set(enabled);
+        self.env().emit_event(AllowlistModeUpdated { enabled });
     }
 
-    /// Get number of validators
-    pub fn get_validator_count(&self) -> u8 {
-        self.validator_count.get_or_default()
+    /// Set the allowlist manager role (owner only)
+    pub fn set_allowlist_manager(&mut self, manager: Address) {
+        self.assert_owner_acting_alone();
+        self.update_allowlist_manager(manager);
     }
 
-    /// Get validator by index
-    pub fn get_validator(&self, index: u8) -> Option<PublicKey> {
-        self.validators.get(&index)
+    fn update_allowlist_manager(&mut self, manager: Address) {
+        self.allowlist_manager.set(manager);
+        self.env().emit_event(AllowlistManagerUpdated { manager });
     }
 
-    /// Check if validator is approved
-    pub fn is_validator_active(&self, validator: PublicKey) -> bool {
-        self.validator_active.get(&validator).unwrap_or(false)
+    /// Add accounts to the allowlist (allowlist manager, or owner while multisig is disabled)
+    pub fn add_to_allowlist(&mut self, accounts: Vec<Address>) {
+        self.assert_allowlist_manager();
+        for account in accounts {
+            self.allowlisted.set(&account, true);
+            self.env().emit_event(AllowlistAdded { account });
+        }
     }
 
-    /// Get amount delegated to a validator
-    pub fn get_delegated_to_validator(&self, validator: PublicKey) -> U512 {
-        self.validator_delegated.get(&validator).unwrap_or(U512::zero())
+    /// Remove accounts from the allowlist (allowlist manager, or owner while multisig is disabled)
+    /// Removed holders can still request_unstake, only inflows are blocked
+    pub fn remove_from_allowlist(&mut self, accounts: Vec<Address>) {
+        self.assert_allowlist_manager();
+        for account in accounts {
+            self.allowlisted.set(&account, false);
+            self.env().emit_event(AllowlistRemoved { account });
+        }
     }
 
-    /// Get withdrawal request CSPR amount by ID
-    pub fn get_withdrawal_amount(&self, request_id: u64) -> U512 {
-        match self.withdrawal_requests.get(&request_id) {
-            Some(request) => request.cspr_amount,
-            None => U512::zero(),
+    fn assert_allowlist_manager(&mut self) {
+        let caller = self.env().caller();
+        if self.allowlist_manager.get() == Some(caller) {
+            return;
+        }
+        if caller == self.ownable.get_owner() {
+            self.assert_owner_acting_alone();
+            return;
         }
+        self.env().revert(Error::NotAllowlistManager);
     }
 
-    /// Get withdrawal request staker by ID
-    pub fn get_withdrawal_staker(&self, request_id: u64) -> Option<Address> {
-        self.withdrawal_requests.get(&request_id).map(|r| r.staker)
+    fn assert_allowlisted(&self, account: &Address) {
+        if self.allowlist_enabled.get_or_default() && !self.allowlisted.get(account).unwrap_or(false) {
+            self.env().revert(Error::NotAllowlisted);
+        }
     }
 
-    /// Check if withdrawal is claimed
-    pub fn is_withdrawal_claimed(&self, request_id: u64) -> bool {
-        match self.withdrawal_requests.get(&request_id) {
-            Some(request) => request.claimed,
-            None => false,
+    // ========================================================================
+    // REFERRALS
+    // ========================================================================
+
+    /// Claim accrued referral rewards as stCSPR
+    pub fn claim_referral_rewards(&mut self) -> U256 {
+        let referrer = self.env().caller();
+        self.assert_allowlisted(&referrer);
+        self.settle_referrer(&referrer);
+
+        let amount = self.referral_rewards.get(&referrer).unwrap_or_default();
+        if amount == U256::zero() {
+            self.env().revert(Error::NoReferralRewards);
         }
+        self.referral_rewards.set(&referrer, U256::zero());
+
+        // Referral stCSPR is minted to the contract at harvest time
+        self.token.raw_transfer(&self.env().self_address(), &referrer, &amount);
+
+        self.env().emit_event(ReferralRewardsClaimed {
+            referrer,
+            stcspr_amount: amount,
+        });
+        amount
     }
 
-    /// Get number of withdrawal requests for a user
-    pub fn get_user_request_count(&self, user: Address) -> u64 {
-        self.user_request_count.get(&user).unwrap_or(0)
+    /// Set share of the protocol fee paid to referrers (owner only)
+    /// Only splits the fee, stakers' share of rewards is unaffected
+    pub fn set_referral_fee_share_bps(&mut self, referral_fee_share_bps: u16) {
+        self.assert_owner_acting_alone();
+        self.update_referral_fee_share(referral_fee_share_bps);
     }
 
-    /// Get user's withdrawal request ID by index
-    pub fn get_user_request_id(&self, user: Address, index: u64) -> Option<u64> {
-        self.user_requests.get(&(user, index))
+    fn update_referral_fee_share(&mut self, referral_fee_share_bps: u16) {
+        if referral_fee_share_bps > BPS_DENOMINATOR {
+            self.env().revert(Error::InvalidBasisPoints);
+        }
+        self.referral_fee_share_bps.set(referral_fee_share_bps);
+        self.env().emit_event(ProtocolFeeUpdated {
+            protocol_fee_bps: self.protocol_fee_bps.get_or_default(),
+            referral_fee_share_bps,
+        });
     }
 
-    /// Check if withdrawal is ready to claim
-    pub fn is_withdrawal_ready(&self, request_id: u64) -> bool {
-        match self.withdrawal_requests.get(&request_id) {
-            Some(request) => {
-                !request.claimed &&
-                self.env().get_block_time() >= request.request_block + UNBONDING_BLOCKS
+    fn record_referral(&mut self, staker: Address, referrer: Address, cspr_amount: U512) {
+        if referrer == staker {
+            self.env().revert(Error::SelfReferral);
+        }
+
+        // A new referrer only once nothing is attributed to the previous one
+        let principal = self.referred_principal.get(&staker).unwrap_or_default();
+        match self.staker_referrer.get(&staker) {
+            Some(existing) if existing == referrer => {}
+            Some(_) if principal > U512::zero() => self.env().revert(Error::ReferrerMismatch),
+            _ => {
+                self.staker_referrer.set(&staker, referrer);
+                let count = self.referral_count.get(&referrer).unwrap_or(0);
+                self.referral_count.set(&referrer, count + 1);
             }
-            None => false,
+        }
+
+        self.settle_referrer(&referrer);
+
+        self.referred_principal.set(&staker, principal + cspr_amount);
+        let tvl = self.referred_tvl.get(&referrer).unwrap_or_default();
+        self.referred_tvl.set(&referrer, tvl + cspr_amount);
+        let total = self.total_referred_tvl.get_or_default();
+        self.total_referred_tvl.set(total + cspr_amount);
+
+        self.env().emit_event(ReferralRecorded {
+            staker,
+            referrer,
+            cspr_amount,
+        });
+    }
+
+    fn release_referral(&mut self, staker: Address, cspr_amount: U512) {
+        let referrer = match self.staker_referrer.get(&staker) {
+            Some(referrer) => referrer,
+            None => return,
+        };
+        let principal = self.referred_principal.get(&staker).unwrap_or_default();
+        let released = principal.min(cspr_amount);
+        if released == U512::zero() {
+            return;
+        }
+
+        self.settle_referrer(&referrer);
+
+        self.referred_principal.set(&staker, principal - released);
+        let tvl = self.referred_tvl.get(&referrer).unwrap_or_default();
+        self.referred_tvl.set(&referrer, tvl.saturating_sub(released));
+        let total = self.total_referred_tvl.get_or_default();
+        self.total_referred_tvl.set(total.saturating_sub(released));
+    }
+
+    /// Release the part of `owner`'s referred principal backing `shares`
+    /// about to leave their balance: a referrer is paid on principal its
+    /// staker still holds, not on stCSPR passed on to someone else.
+    fn release_referral_for_shares(&mut self, owner: Address, shares: U256) {
+        let balance = self.token.balance_of(&owner);
+        if shares == U256::zero() || balance == U256::zero() {
+            return;
+        }
+        let principal = self.referred_principal.get(&owner).unwrap_or_default();
+        let released = principal * u256_to_u512(shares.min(balance)) / u256_to_u512(balance);
+        self.release_referral(owner, released);
+    }
+
+    /// Move a referrer's accrued rewards into referral_rewards
+    fn settle_referrer(&mut self, referrer: &Address) {
+        let pending = self.pending_referral_rewards(referrer);
+        let acc = self.referral_acc_per_tvl.get_or_default();
+        self.referral_checkpoint.set(referrer, acc);
+        self.referral_rewards.set(referrer, pending);
+    }
+
+    fn pending_referral_rewards(&self, referrer: &Address) -> U256 {
+        let settled = self.referral_rewards.get(referrer).unwrap_or_default();
+        let tvl = self.referred_tvl.get(referrer).unwrap_or_default();
+        let acc = self.referral_acc_per_tvl.get_or_default();
+        let checkpoint = self.referral_checkpoint.get(referrer).unwrap_or_default();
+        let accrued = tvl * (acc - checkpoint) / U512::from(ACC_PRECISION);
+        settled + u512_to_u256(accrued)
+    }
+
+    /// Mint the protocol fee on a harvest as stCSPR
+    ///
+    /// Called after the reward is added to the pool. Mints shares worth
+    /// `fee` at the post-harvest rate. Referrers get their share only of
+    /// the fee on rewards earned by referred principal, i.e.
+    /// `fee * total_referred / pool * referral_fee_share_bps`, minted to the
+    /// contract and claimed later. The rest goes to the owner.
+    fn mint_protocol_fee(&mut self, reward_amount: U512) {
+        let fee_bps = self.protocol_fee_bps.get_or_default();
+        let total_stcspr = u256_to_u512(self.token.total_supply());
+        if fee_bps == 0 || total_stcspr == U512::zero() {
+            return;
+        }
+
+        let fee = reward_amount * U512::from(fee_bps) / U512::from(BPS_DENOMINATOR);
+        let pool = self.total_cspr_pool.get_or_default();
+        if fee == U512::zero() || fee >= pool {
+            return;
+        }
+        let fee_shares = fee * total_stcspr / (pool - fee);
+
+        // Referral part pro rata to the referred share of the pool that earned the reward
+        let earning_pool = pool - reward_amount;
+        let total_referred = self.total_referred_tvl.get_or_default().min(earning_pool);
+        let mut referral_shares = U512::zero();
+        if total_referred > U512::zero() {
+            let share_bps = self.referral_fee_share_bps.get_or_default();
+            referral_shares = fee_shares * U512::from(share_bps) * total_referred
+                / (U512::from(BPS_DENOMINATOR) * earning_pool);
+        }
+        let owner_shares = fee_shares - referral_shares;
+
+        if referral_shares > U512::zero() {
+            self.token.raw_mint(&self.env().self_address(), &u512_to_u256(referral_shares));
+            let acc = self.referral_acc_per_tvl.get_or_default();
+            self.referral_acc_per_tvl.set(
+                acc + referral_shares * U512::from(ACC_PRECISION)
+                    / self.total_referred_tvl.get_or_default(),
+            );
+        }
+        if owner_shares > U512::zero() {
+            self.token.raw_mint(&self.ownable.get_owner(), &u512_to_u256(owner_shares));
         }
     }
 
-    /// Get token info
-    pub fn token_name(&self) -> String {
-        self.token.name()
-    }
+    // ========================================================================
+    // EXCHANGE RATE FUNCTIONS
+    // ========================================================================
+
+    /// Get current exchange rate (with 9 decimal precision)
+    pub fn get_exchange_rate(&self) -> U512 {
+        let total_cspr = self.total_cspr_pool.get_or_default();
+        let total_stcspr = self.token.total_supply();
+
+        if total_stcspr == U256::zero() {
+            return U512::from(RATE_PRECISION);
+        }
+
+        let precision = U512::from(RATE_PRECISION);
+        let total_stcspr_512 = u256_to_u512(total_stcspr);
+
+        (total_cspr * precision) / total_stcspr_512
+    }
+
+    /// Record the current exchange rate in the history (owner only)
+    /// Harvests record automatically, use this after other pool changes
+    pub fn sync_rate_snapshot(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.record_rate_snapshot();
+    }
+
+    /// Get snapshot by absolute index (None if not recorded yet or
+    /// already overwritten by newer snapshots)
+    pub fn get_rate_at(&self, index: u64) -> Option<RateSnapshot> {
+        let count = self.rate_snapshot_count.get_or_default();
+        if index >= count || index < self.oldest_rate_index() {
+            return None;
+        }
+        self.rate_snapshots.get(&(index % RATE_HISTORY_SIZE))
+    }
+
+    /// Get up to `limit` snapshots, oldest first, starting `offset`
+    /// snapshots after the oldest one still stored
+    pub fn get_rate_history(&self, offset: u64, limit: u64) -> Vec<RateSnapshot> {
+        let count = self.rate_snapshot_count.get_or_default();
+        let start = self.oldest_rate_index().saturating_add(offset);
+        let end = count.min(start.saturating_add(limit.min(MAX_RATE_HISTORY_PAGE)));
+        (start..end).filter_map(|index| self.get_rate_at(index)).collect()
+    }
+
+    /// Get number of snapshots ever recorded
+    pub fn get_rate_snapshot_count(&self) -> u64 {
+        self.rate_snapshot_count.get_or_default()
+    }
+
+    /// Estimate APR in basis points from the latest snapshot and the one
+    /// `lookback` snapshots before it (clamped to the oldest stored)
+    pub fn get_estimated_apr_bps(&self, lookback: u64) -> u64 {
+        let count = self.rate_snapshot_count.get_or_default();
+        if count < 2 || lookback == 0 {
+            return 0;
+        }
+        let latest_index = count - 1;
+        let from_index = latest_index.saturating_sub(lookback).max(self.oldest_rate_index());
+
+        let (latest, from) = match (self.get_rate_at(latest_index), self.get_rate_at(from_index)) {
+            (Some(latest), Some(from)) => (latest, from),
+            _ => return 0,
+        };
+        let elapsed = latest.timestamp.saturating_sub(from.timestamp);
+        if elapsed == 0 || from.rate == U512::zero() || latest.rate <= from.rate {
+            return 0;
+        }
+
+        let growth = latest.rate - from.rate;
+        let apr = growth * U512::from(BPS_DENOMINATOR) * U512::from(YEAR_MS)
+            / (from.rate * U512::from(elapsed));
+        if apr > U512::from(u64::MAX) {
+            u64::MAX
+        } else {
+            apr.as_u64()
+        }
+    }
+
+    fn record_rate_snapshot(&mut self) {
+        let index = self.rate_snapshot_count.get_or_default();
+        self.rate_snapshots.set(&(index % RATE_HISTORY_SIZE), RateSnapshot {
+            timestamp: self.env().get_block_time(),
+            rate: self.get_exchange_rate(),
+            total_pool: self.total_cspr_pool.get_or_default(),
+            total_supply: self.token.total_supply(),
+        });
+        self.rate_snapshot_count.set(index + 1);
+    }
+
+    fn oldest_rate_index(&self) -> u64 {
+        self.rate_snapshot_count.get_or_default().saturating_sub(RATE_HISTORY_SIZE)
+    }
+
+    fn spend_allowance(&mut self, owner: Address, spender: Address, amount: U256) {
+        let allowance = self.allowances.get(&(owner, spender)).unwrap_or_default();
+        if amount > allowance {
+            self.env().revert(Error::InsufficientAllowance);
+        }
+        self.allowances.set(&(owner, spender), allowance - amount);
+    }
+
+    fn move_shares(&mut self, owner: Address, recipient: Address, amount: U256) {
+        self.assert_allowlisted(&owner);
+        self.assert_allowlisted(&recipient);
+        if recipient != owner {
+            self.release_referral_for_shares(owner, amount);
+        }
+        self.token.raw_transfer(&owner, &recipient, &amount);
+    }
+
+    fn cspr_to_stcspr(&self, cspr_amount: U512) -> U256 {
+        let total_cspr = self.total_cspr_pool.get_or_default();
+        let total_stcspr = self.token.total_supply();
+
+        if total_stcspr == U256::zero() || total_cspr == U512::zero() {
+            return u512_to_u256(cspr_amount);
+        }
+
+        let total_stcspr_512 = u256_to_u512(total_stcspr);
+        let result = (cspr_amount * total_stcspr_512) / total_cspr;
+        u512_to_u256(result)
+    }
+
+    fn stcspr_to_cspr(&self, stcspr_amount: U256) -> U512 {
+        let total_cspr = self.total_cspr_pool.get_or_default();
+        let total_stcspr = self.token.total_supply();
+
+        if total_stcspr == U256::zero() {
+            return U512::zero();
+        }
+
+        let stcspr_512 = u256_to_u512(stcspr_amount);
+        let total_stcspr_512 = u256_to_u512(total_stcspr);
+
+        (stcspr_512 * total_cspr) / total_stcspr_512
+    }
+
+    // ========================================================================
+    // ADMIN FUNCTIONS (V20 - Pool Management)
+    // ========================================================================
+
+    /// Delegate pool funds to a validator (owner only)
+    ///
+    /// V20: Admin controls when to delegate. Takes CSPR from available_liquidity
+    /// and delegates to the specified validator.
+    pub fn admin_delegate(&mut self, validator: PublicKey, amount: U512) {
+        self.assert_owner_acting_alone();
+        self.delegate_pool_funds(validator, amount);
+    }
+
+    fn delegate_pool_funds(&mut self, validator: PublicKey, amount: U512) {
+        self.assert_not_emergency();
+
+        if amount == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
+        }
+
+        // Check validator is approved
+        if !self.validator_active.get(&validator).unwrap_or(false) {
+            self.env().revert(Error::ValidatorNotApproved);
+        }
+
+        // Check we have enough liquidity to delegate
+        let liquidity = self.available_liquidity.get_or_default();
+        if amount > liquidity {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+
+        // Check minimum delegation requirement
+        let current_delegated = self.validator_delegated.get(&validator).unwrap_or(U512::zero());
+        if current_delegated == U512::zero() && amount < U512::from(MIN_DELEGATION) {
+            self.env().revert(Error::BelowMinimumDelegation);
+        }
+
+        // Check per-validator cap and concentration limit
+        if amount > self.cap_headroom(&validator) {
+            self.env().revert(Error::ValidatorCapExceeded);
+        }
+        if amount > self.share_headroom(&validator) {
+            self.env().revert(Error::ConcentrationLimitExceeded);
+        }
+
+        // Reduce available liquidity
+        self.available_liquidity.set(liquidity - amount);
+
+        // Update validator delegated amount
+        self.validator_delegated.set(&validator, current_delegated + amount);
+
+        // Actually delegate to auction contract
+        #[cfg(not(test))]
+        {
+            self.env().delegate(validator.clone(), amount);
+        }
+
+        self.env().emit_event(AdminDelegated {
+            validator,
+            amount,
+        });
+    }
+
+    /// Undelegate from a validator (owner only)
+    ///
+    /// V20: Admin calls this to undelegate. After unbonding period,
+    /// call admin_credit_purse_liquidity() when the returned CSPR is in the
+    /// contract purse, or admin_add_liquidity() to send it from the owner.
+    pub fn admin_undelegate(&mut self, validator: PublicKey, amount: U512) {
+        self.assert_owner_acting_alone();
+        self.undelegate_pool_funds(validator, amount);
+    }
+
+    fn undelegate_pool_funds(&mut self, validator: PublicKey, amount: U512) {
+        if amount == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
+        }
+
+        // Check validator has enough delegated
+        let delegated = self.validator_delegated.get(&validator).unwrap_or(U512::zero());
+        if amount > delegated {
+            self.env().revert(Error::UndelegateAmountExceedsDelegation);
+        }
+
+        // Update validator delegated amount
+        self.validator_delegated.set(&validator, delegated - amount);
+
+        // Reduce pending undelegations (this amount is now being processed)
+        let pending_undel = self.pending_undelegations.get_or_default();
+        if amount <= pending_undel {
+            self.pending_undelegations.set(pending_undel - amount);
+        } else {
+            self.pending_undelegations.set(U512::zero());
+        }
+
+        // Actually undelegate from auction contract
+        #[cfg(not(test))]
+        {
+            self.env().undelegate(validator.clone(), amount);
+        }
+
+        self.env().emit_event(AdminUndelegated {
+            validator,
+            amount,
+        });
+    }
+
+    /// Add liquidity to the pool (owner only)
+    ///
+    /// V20: After unbonding completes, admin calls this to add the
+    /// returned CSPR back to available_liquidity for claims.
+    #[odra(payable)]
+    pub fn admin_add_liquidity(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.record_admin_activity();
+
+        let amount = self.env().attached_value();
+        if amount == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
+        }
+
+        // Add to available liquidity
+        let liquidity = self.available_liquidity.get_or_default();
+        self.available_liquidity.set(liquidity + amount);
+
+        self.env().emit_event(LiquidityAdded { amount });
+    }
+
+    /// Credit CSPR already in the contract purse to available_liquidity
+    /// (owner only)
+    ///
+    /// For unbonded CSPR the auction paid into the purse: only the surplus
+    /// over liquidity, the legacy reserve and emergency-unbonded CSPR can
+    /// be credited.
+    pub fn admin_credit_purse_liquidity(&mut self, amount: U512) {
+        self.assert_owner_acting_alone();
+        self.credit_purse_liquidity(amount);
+    }
+
+    fn credit_purse_liquidity(&mut self, amount: U512) {
+        if amount == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
+        }
+
+        if amount > self.get_purse_surplus() {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+        let liquidity = self.available_liquidity.get_or_default();
+        self.available_liquidity.set(liquidity + amount);
+
+        self.env().emit_event(LiquidityAdded { amount });
+    }
+
+    /// Add a validator to the approved list (owner only)
+    pub fn add_validator(&mut self, validator: PublicKey) {
+        self.assert_owner_acting_alone();
+        self.register_validator(validator);
+    }
+
+    fn register_validator(&mut self, validator: PublicKey) {
+        // Check not already active
+        if self.validator_active.get(&validator).unwrap_or(false) {
+            self.env().revert(Error::ValidatorAlreadyExists);
+        }
+
+        // Check max validators
+        let count = self.validator_count.get_or_default();
+        if count as usize >= MAX_VALIDATORS {
+            self.env().revert(Error::MaxValidatorsReached);
+        }
+
+        // Add validator
+        self.validators.set(&count, validator.clone());
+        self.validator_active.set(&validator, true);
+        self.validator_delegated.set(&validator, U512::zero());
+        self.validator_count.set(count + 1);
+
+        // Keep existing metadata if the validator is re-added after removal
+        if self.validator_info.get(&validator).is_none() {
+            self.validator_info.set(&validator, ValidatorInfo {
+                name: String::new(),
+                commission_rate: 0,
+                max_allocation: U512::zero(),
+                added_at: self.env().get_block_time(),
+                notes: String::new(),
+            });
+        }
+
+        self.env().emit_event(ValidatorAdded { validator });
+    }
+
+    /// Remove a validator from the approved list (via timelock)
+    /// Note: Does not affect existing delegations
+    fn remove_validator(&mut self, validator: PublicKey) {
+        self.validator_active.set(&validator, false);
+        self.env().emit_event(ValidatorRemoved { validator });
+    }
+
+    /// Set validator display name (owner only)
+    pub fn set_validator_name(&mut self, validator: PublicKey, name: String) {
+        self.ownable.assert_owner(&self.env().caller());
+        let mut info = self.validator_info_or_revert(&validator);
+        info.name = name;
+        self.save_validator_info(validator, info);
+    }
+
+    /// Set validator commission snapshot in percent (owner only)
+    pub fn set_validator_commission(&mut self, validator: PublicKey, commission_rate: u8) {
+        self.ownable.assert_owner(&self.env().caller());
+        if commission_rate > MAX_COMMISSION_RATE {
+            self.env().revert(Error::InvalidCommissionRate);
+        }
+        let mut info = self.validator_info_or_revert(&validator);
+        info.commission_rate = commission_rate;
+        self.save_validator_info(validator, info);
+    }
+
+    /// Set maximum CSPR the pool may delegate to a validator (owner only)
+    /// Zero means no cap
+    pub fn set_validator_max_allocation(&mut self, validator: PublicKey, max_allocation: U512) {
+        self.assert_owner_acting_alone();
+        self.update_validator_max_allocation(validator, max_allocation);
+    }
+
+    fn update_validator_max_allocation(&mut self, validator: PublicKey, max_allocation: U512) {
+        let mut info = self.validator_info_or_revert(&validator);
+        info.max_allocation = max_allocation;
+        self.save_validator_info(validator, info);
+    }
+
+    /// Set validator operator notes (owner only)
+    pub fn set_validator_notes(&mut self, validator: PublicKey, notes: String) {
+        self.ownable.assert_owner(&self.env().caller());
+        let mut info = self.validator_info_or_revert(&validator);
+        info.notes = notes;
+        self.save_validator_info(validator, info);
+    }
+
+    fn validator_info_or_revert(&self, validator: &PublicKey) -> ValidatorInfo {
+        match self.validator_info.get(validator) {
+            Some(info) => info,
+            None => self.env().revert(Error::ValidatorNotFound),
+        }
+    }
+
+    fn save_validator_info(&mut self, validator: PublicKey, info: ValidatorInfo) {
+        self.validator_info.set(&validator, info);
+        self.env().emit_event(ValidatorInfoUpdated { validator });
+    }
+
+    /// Set max share of total pool any single validator may hold (owner only)
+    /// In basis points (10_000 = 100%), zero disables the limit
+    pub fn set_max_validator_share_bps(&mut self, max_validator_share_bps: u16) {
+        self.assert_owner_acting_alone();
+        self.update_max_validator_share(max_validator_share_bps);
+    }
+
+    fn update_max_validator_share(&mut self, max_validator_share_bps: u16) {
+        if max_validator_share_bps > BPS_DENOMINATOR {
+            self.env().revert(Error::InvalidBasisPoints);
+        }
+        self.max_validator_share_bps.set(max_validator_share_bps);
+        self.env().emit_event(ConcentrationLimitUpdated { max_validator_share_bps });
+    }
+
+    /// Remaining CSPR under the validator's absolute cap (U512::MAX if uncapped)
+    fn cap_headroom(&self, validator: &PublicKey) -> U512 {
+        let cap = self.validator_info.get(validator)
+            .map(|info| info.max_allocation)
+            .unwrap_or(U512::zero());
+        if cap == U512::zero() {
+            return U512::MAX;
+        }
+        let delegated = self.validator_delegated.get(validator).unwrap_or(U512::zero());
+        cap.saturating_sub(delegated)
+    }
+
+    /// Remaining CSPR under the concentration limit (U512::MAX if disabled)
+    fn share_headroom(&self, validator: &PublicKey) -> U512 {
+        let bps = self.max_validator_share_bps.get_or_default();
+        if bps == 0 {
+            return U512::MAX;
+        }
+        let pool = self.total_cspr_pool.get_or_default();
+        let max_share = pool * U512::from(bps) / U512::from(BPS_DENOMINATOR);
+        let delegated = self.validator_delegated.get(validator).unwrap_or(U512::zero());
+        max_share.saturating_sub(delegated)
+    }
+
+    /// Set global TVL cap on total pool (owner only), zero disables it
+    pub fn set_tvl_cap(&mut self, tvl_cap: U512) {
+        self.assert_owner_acting_alone();
+        self.update_tvl_cap(tvl_cap);
+    }
+
+    fn update_tvl_cap(&mut self, tvl_cap: U512) {
+        self.tvl_cap.set(tvl_cap);
+        self.emit_deposit_limits();
+    }
+
+    /// Set max CSPR value per account (owner only), zero disables it
+    pub fn set_max_stake_per_account(&mut self, max_stake: U512) {
+        self.assert_owner_acting_alone();
+        self.update_max_stake_per_account(max_stake);
+    }
+
+    fn update_max_stake_per_account(&mut self, max_stake: U512) {
+        self.max_stake_per_account.set(max_stake);
+        self.emit_deposit_limits();
+    }
+
+    /// Set minimum CSPR per stake call (owner only), zero disables it
+    pub fn set_min_stake_amount(&mut self, min_stake: U512) {
+        self.assert_owner_acting_alone();
+        self.update_min_stake_amount(min_stake);
+    }
+
+    fn update_min_stake_amount(&mut self, min_stake: U512) {
+        self.min_stake_amount.set(min_stake);
+        self.emit_deposit_limits();
+    }
+
+    fn emit_deposit_limits(&self) {
+        self.env().emit_event(DepositLimitsUpdated {
+            tvl_cap: self.tvl_cap.get_or_default(),
+            max_stake_per_account: self.max_stake_per_account.get_or_default(),
+            min_stake_amount: self.min_stake_amount.get_or_default(),
+        });
+    }
+
+    fn assert_deposit_limits(&self, account: &Address, cspr_amount: U512) {
+        if cspr_amount < self.min_stake_amount.get_or_default() {
+            self.env().revert(Error::BelowMinimumStake);
+        }
+        if cspr_amount > self.get_remaining_capacity() {
+            self.env().revert(Error::TvlCapExceeded);
+        }
+        if cspr_amount > self.account_headroom(account) {
+            self.env().revert(Error::AccountStakeLimitExceeded);
+        }
+    }
+
+    /// Remaining CSPR an account may stake under the per-account limit (U512::MAX if disabled)
+    fn account_headroom(&self, account: &Address) -> U512 {
+        let max_stake = self.max_stake_per_account.get_or_default();
+        if max_stake == U512::zero() {
+            return U512::MAX;
+        }
+        let current = self.stcspr_to_cspr(self.token.balance_of(account));
+        max_stake.saturating_sub(current)
+    }
+
+    /// Harvest rewards and add to pool (owner only)
+    /// This increases the exchange rate
+    /// Limited to 10% of pool per call to prevent manipulation
+    #[odra(payable)]
+    pub fn harvest_rewards(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.record_admin_activity();
+
+        let reward_amount = self.env().attached_value();
+        if reward_amount == U512::zero() {
+            self.env().revert(Error::ZeroAmount);
+        }
+
+        let pool = self.total_cspr_pool.get_or_default();
+
+        // Security: limit rewards to 10% of current pool to prevent manipulation
+        let max_reward = pool / U512::from(10); // 10% max
+        if reward_amount > max_reward && pool > U512::zero() {
+            self.env().revert(Error::RewardsTooHigh);
+        }
+
+        self.total_cspr_pool.set(pool + reward_amount);
+
+        self.mint_protocol_fee(reward_amount);
+        self.record_rate_snapshot();
+
+        let new_rate = self.get_exchange_rate();
+        self.env().emit_event(RewardsHarvested {
+            amount: reward_amount,
+            new_exchange_rate: new_rate,
+        });
+    }
+
+    // ========================================================================
+    // EMERGENCY MODE (dead-man's switch)
+    // ========================================================================
+
+    /// Prove the operator is alive without doing anything else (owner only)
+    pub fn heartbeat(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.record_admin_activity();
+    }
+
+    /// Set admin inactivity period before emergency mode (via timelock)
+    fn set_emergency_timeout(&mut self, timeout: u64) {
+        self.emergency_timeout.set(timeout);
+    }
+
+    /// Enter emergency mode (anyone, once the owner has been inactive
+    /// for emergency_timeout). Stops new stakes and delegations and lets
+    /// withdrawal holders exit without the owner. Cannot be undone.
+    pub fn trigger_emergency_mode(&mut self) {
+        self.assert_not_emergency();
+
+        let last_activity = self.last_admin_activity.get_or_default();
+        let timeout = self.emergency_timeout.get_or_default();
+        if self.env().get_block_time() < last_activity.saturating_add(timeout) {
+            self.env().revert(Error::AdminStillActive);
+        }
+
+        self.emergency_mode.set(true);
+        self.env().emit_event(EmergencyModeTriggered {
+            triggered_by: self.env().caller(),
+            last_admin_activity: last_activity,
+        });
+    }
+
+    /// Undelegate the CSPR for a withdrawal request (emergency mode only)
+    ///
+    /// Takes the request amount pro-rata from every validator the pool
+    /// delegates to, capped at the outstanding pending_undelegations (the
+    /// rest was already undelegated by the owner). After the unbonding
+    /// period the CSPR is back in the contract purse and the staker calls
+    /// emergency_claim().
+    pub fn emergency_undelegate(&mut self, request_id: u64) {
+        if !self.emergency_mode.get_or_default() {
+            self.env().revert(Error::EmergencyModeNotActive);
+        }
+
+        let request = match self.withdrawal_requests.get(&request_id) {
+            Some(request) => request,
+            None => self.env().revert(Error::WithdrawalNotFound),
+        };
+        if request.staker != self.env().caller() {
+            self.env().revert(Error::NotWithdrawalOwner);
+        }
+        if request.claimed {
+            self.env().revert(Error::WithdrawalAlreadyClaimed);
+        }
+        if self.emergency_undelegated_at.get(&request_id).is_some() {
+            self.env().revert(Error::EmergencyAlreadyUndelegated);
+        }
+
+        // Collect current delegations
+        let mut delegations: Vec<(PublicKey, U512)> = Vec::new();
+        let mut total_delegated = U512::zero();
+        for index in 0..self.validator_count.get_or_default() {
+            if let Some(validator) = self.validators.get(&index) {
+                let delegated = self.validator_delegated.get(&validator).unwrap_or(U512::zero());
+                if delegated > U512::zero() {
+                    total_delegated += delegated;
+                    delegations.push((validator, delegated));
+                }
+            }
+        }
+        if total_delegated == U512::zero() {
+            self.env().revert(Error::NothingToUndelegate);
+        }
+
+        // Never undelegate more than unstake demand still waiting for it
+        let pending_undel = self.pending_undelegations.get_or_default();
+        let to_undelegate = request.cspr_amount.min(total_delegated).min(pending_undel);
+        if to_undelegate == U512::zero() {
+            self.env().revert(Error::NothingToUndelegate);
+        }
+
+        // Split pro-rata, last validator takes the rounding remainder
+        let mut remaining = to_undelegate;
+        let last = delegations.len() - 1;
+        for (i, (validator, delegated)) in delegations.into_iter().enumerate() {
+            let share = if i == last {
+                remaining.min(delegated)
+            } else {
+                to_undelegate * delegated / total_delegated
+            };
+            if share == U512::zero() {
+                continue;
+            }
+            remaining -= share;
+            self.validator_delegated.set(&validator, delegated - share);
+
+            #[cfg(not(test))]
+            {
+                self.env().undelegate(validator.clone(), share);
+            }
+        }
+
+        let undelegated = to_undelegate - remaining;
+        self.pending_undelegations.set(pending_undel - undelegated);
+        self.emergency_undelegated_at.set(&request_id, self.env().get_block_time());
+        self.emergency_undelegated.set(&request_id, undelegated);
+        let unbonding = self.emergency_unbonding.get_or_default();
+        self.emergency_unbonding.set(unbonding + undelegated);
+
+        self.env().emit_event(EmergencyUndelegated {
+            staker: request.staker,
+            request_id,
+            cspr_amount: undelegated,
+        });
+    }
+
+    /// Claim an emergency-undelegated withdrawal once unbonding is over
+    ///
+    /// The CSPR this request undelegated is paid from the contract purse
+    /// directly: it never went through admin_add_liquidity(). Whatever
+    /// the owner had already undelegated for it comes from available_liquidity.
+    pub fn emergency_claim(&mut self, request_id: u64) {
+        let caller = self.env().caller();
+
+        let mut request = match self.withdrawal_requests.get(&request_id) {
+            Some(request) => request,
+            None => self.env().revert(Error::WithdrawalNotFound),
+        };
+        if request.staker != caller {
+            self.env().revert(Error::NotWithdrawalOwner);
+        }
+        if request.claimed {
+            self.env().revert(Error::WithdrawalAlreadyClaimed);
+        }
+
+        let undelegated_at = match self.emergency_undelegated_at.get(&request_id) {
+            Some(time) => time,
+            None => self.env().revert(Error::EmergencyNotUndelegated),
+        };
+        if self.env().get_block_time() < undelegated_at.saturating_add(UNBONDING_BLOCKS) {
+            self.env().revert(Error::WithdrawalNotReady);
+        }
+
+        let unbonded = self.emergency_undelegated.get(&request_id).unwrap_or_default();
+        let from_unbonded = unbonded.min(request.cspr_amount);
+        let from_liquidity = request.cspr_amount - from_unbonded;
+
+        let liquidity = self.available_liquidity.get_or_default();
+        if from_liquidity > liquidity {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+        // The unbonded part must have landed, without touching pool liquidity
+        // or the legacy reserve
+        let held = liquidity + self.legacy_reserve.get_or_default();
+        if from_unbonded > self.env().self_balance().saturating_sub(held) {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+
+        request.claimed = true;
+        self.withdrawal_requests.set(&request_id, request.clone());
+
+        let pending = self.pending_withdrawals.get_or_default();
+        self.pending_withdrawals.set(pending - request.cspr_amount);
+        self.available_liquidity.set(liquidity - from_liquidity);
+        let unbonding = self.emergency_unbonding.get_or_default();
+        self.emergency_unbonding.set(unbonding.saturating_sub(from_unbonded));
+
+        let receiver = self.get_withdrawal_receiver(request_id).unwrap_or(caller);
+        self.env().transfer_tokens(&receiver, &request.cspr_amount);
+
+        self.env().emit_event(Claimed {
+            staker: caller,
+            request_id,
+            cspr_amount: request.cspr_amount,
+        });
+    }
+
+    fn record_admin_activity(&mut self) {
+        self.last_admin_activity.set(self.env().get_block_time());
+    }
+
+    fn assert_not_emergency(&self) {
+        if self.emergency_mode.get_or_default() {
+            self.env().revert(Error::EmergencyModeActive);
+        }
+    }
+
+    /// Set protocol fee on harvested rewards (via timelock)
+    fn set_protocol_fee(&mut self, protocol_fee_bps: u16) {
+        self.protocol_fee_bps.set(protocol_fee_bps);
+        self.env().emit_event(ProtocolFeeUpdated {
+            protocol_fee_bps,
+            referral_fee_share_bps: self.referral_fee_share_bps.get_or_default(),
+        });
+    }
+
+    /// Transfer ownership (via timelock)
+    fn transfer_ownership(&mut self, new_owner: Address) {
+        self.ownable.transfer_ownership(&new_owner);
+    }
+
+    // ========================================================================
+    // TIMELOCK (sensitive admin operations)
+    // ========================================================================
+
+    /// Queue validator removal (owner only), executable from `eta`
+    pub fn queue_remove_validator(&mut self, validator: PublicKey, eta: u64) -> u64 {
+        self.assert_owner_acting_alone();
+        self.queue_operation(OperationKind::RemoveValidator, Some(validator), None, 0, eta)
+    }
+
+    /// Queue ownership transfer (owner only), executable from `eta`
+    pub fn queue_transfer_ownership(&mut self, new_owner: Address, eta: u64) -> u64 {
+        self.assert_owner_acting_alone();
+        self.queue_operation(OperationKind::TransferOwnership, None, Some(new_owner), 0, eta)
+    }
+
+    /// Queue emergency timeout change (owner only), executable from `eta`
+    pub fn queue_set_emergency_timeout(&mut self, timeout: u64, eta: u64) -> u64 {
+        self.assert_owner_acting_alone();
+        self.queue_emergency_timeout(timeout, eta)
+    }
+
+    fn queue_emergency_timeout(&mut self, timeout: u64, eta: u64) -> u64 {
+        if timeout == 0 {
+            self.env().revert(Error::ZeroAmount);
+        }
+        self.queue_operation(OperationKind::SetEmergencyTimeout, None, None, timeout, eta)
+    }
+
+    /// Queue protocol fee change (owner only), executable from `eta`
+    pub fn queue_set_protocol_fee(&mut self, protocol_fee_bps: u16, eta: u64) -> u64 {
+        self.assert_owner_acting_alone();
+        self.queue_protocol_fee(protocol_fee_bps, eta)
+    }
+
+    fn queue_protocol_fee(&mut self, protocol_fee_bps: u16, eta: u64) -> u64 {
+        if protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
+            self.env().revert(Error::InvalidBasisPoints);
+        }
+        self.queue_operation(OperationKind::SetProtocolFee, None, None, protocol_fee_bps as u64, eta)
+    }
+
+    /// Execute a queued operation once its ETA has passed (owner only)
+    pub fn execute_operation(&mut self, operation_id: u64) {
+        self.assert_owner_acting_alone();
+        self.run_operation(operation_id);
+    }
+
+    fn run_operation(&mut self, operation_id: u64) {
+        let mut operation = self.queued_operation_or_revert(operation_id);
+        let now = self.env().get_block_time();
+        if now < operation.eta {
+            self.env().revert(Error::OperationNotReady);
+        }
+        if now > operation.eta.saturating_add(TIMELOCK_GRACE_PERIOD) {
+            self.env().revert(Error::OperationExpired);
+        }
+
+        operation.status = OperationStatus::Executed;
+        self.timelock_operations.set(&operation_id, operation.clone());
+
+        match operation.kind {
+            OperationKind::RemoveValidator => {
+                if let Some(validator) = operation.validator {
+                    self.remove_validator(validator);
+                }
+            }
+            OperationKind::TransferOwnership => {
+                if let Some(new_owner) = operation.account {
+                    self.transfer_ownership(new_owner);
+                }
+            }
+            OperationKind::SetEmergencyTimeout => {
+                self.set_emergency_timeout(operation.value);
+            }
+            OperationKind::SetProtocolFee => {
+                self.set_protocol_fee(operation.value as u16);
+            }
+            OperationKind::SetRebasingView => {
+                if let Some(view) = operation.account {
+                    self.set_rebasing_view(view);
+                }
+            }
+        }
+
+        self.env().emit_event(OperationExecuted { operation_id });
+    }
+
+    /// Cancel a queued operation (owner only)
+    pub fn cancel_operation(&mut self, operation_id: u64) {
+        self.assert_owner_acting_alone();
+        self.drop_operation(operation_id);
+    }
+
+    fn drop_operation(&mut self, operation_id: u64) {
+        let mut operation = self.queued_operation_or_revert(operation_id);
+        operation.status = OperationStatus::Cancelled;
+        self.timelock_operations.set(&operation_id, operation);
+
+        self.env().emit_event(OperationCancelled { operation_id });
+    }
+
+    fn queue_operation(
+        &mut self,
+        kind: OperationKind,
+        validator: Option<PublicKey>,
+        account: Option<Address>,
+        value: u64,
+        eta: u64,
+    ) -> u64 {
+        if eta < self.env().get_block_time() + TIMELOCK_DELAY {
+            self.env().revert(Error::TimelockEtaTooSoon);
+        }
+
+        let operation_id = self.next_operation_id.get_or_default();
+        self.next_operation_id.set(operation_id + 1);
+
+        self.timelock_operations.set(&operation_id, TimelockOperation {
+            kind: kind.clone(),
+            validator,
+            account,
+            value,
+            eta,
+            status: OperationStatus::Queued,
+        });
+
+        self.env().emit_event(OperationQueued { operation_id, kind, eta });
+        operation_id
+    }
+
+    // ========================================================================
+    // MULTISIG (M-of-N approvals for admin actions)
+    // ========================================================================
+
+    /// Register a multisig approver
+    /// Owner only while multisig is disabled, afterwards via AddApprover proposal
+    pub fn add_approver(&mut self, approver: Address) {
+        self.assert_owner_acting_alone();
+        self.register_approver(approver);
+    }
+
+    /// Unregister a multisig approver
+    /// Owner only while multisig is disabled, afterwards via RemoveApprover proposal
+    pub fn remove_approver(&mut self, approver: Address) {
+        self.assert_owner_acting_alone();
+        self.unregister_approver(approver);
+    }
+
+    /// Set approvals required per proposal, enabling multisig when non-zero
+    /// Owner only while multisig is disabled, afterwards via SetApprovalThreshold proposal
+    pub fn set_approval_threshold(&mut self, threshold: u8) {
+        self.assert_owner_acting_alone();
+        self.update_approval_threshold(threshold);
+    }
+
+    /// Propose an admin action (approvers only), auto-approved by the proposer
+    ///
+    /// `validator` is the validator acted on and `account` the account
+    /// (new owner, approver, allowlist manager or rebasing view). `amount`
+    /// carries the CSPR amount for delegate/undelegate, CreditPurseLiquidity,
+    /// SetValidatorMaxAllocation and the deposit limits, the request ID for
+    /// AdminProcessClaim, the operation ID for Execute/CancelOperation, the
+    /// threshold for SetApprovalThreshold, the bps for SetMaxValidatorShare,
+    /// SetProtocolFee and SetReferralFeeShare, the timeout for
+    /// SetEmergencyTimeout and non-zero to enable for SetAllowlistEnabled.
+    pub fn create_proposal(
+        &mut self,
+        kind: ProposalKind,
+        validator: Option<PublicKey>,
+        account: Option<Address>,
+        amount: U512,
+    ) -> u64 {
+        let proposer = self.env().caller();
+        self.assert_approver(&proposer);
+
+        let proposal_id = self.next_proposal_id.get_or_default();
+        self.next_proposal_id.set(proposal_id + 1);
+
+        self.proposals.set(&proposal_id, Proposal {
+            kind: kind.clone(),
+            proposer,
+            validator,
+            account,
+            amount,
+            approvals: 0,
+            executed: false,
+        });
+        self.env().emit_event(ProposalCreated { proposal_id, kind, proposer });
+
+        self.approve_proposal(proposal_id);
+        proposal_id
+    }
+
+    /// Approve a proposal (approvers only, once each)
+    pub fn approve_proposal(&mut self, proposal_id: u64) {
+        let approver = self.env().caller();
+        self.assert_approver(&approver);
+
+        let mut proposal = self.pending_proposal_or_revert(proposal_id);
+        if self.proposal_approvals.get(&(proposal_id, approver)).unwrap_or(false) {
+            self.env().revert(Error::AlreadyApproved);
+        }
+
+        self.proposal_approvals.set(&(proposal_id, approver), true);
+        self.proposal_approver_at.set(&(proposal_id, proposal.approvals), approver);
+        proposal.approvals += 1;
+        let approvals = proposal.approvals;
+        self.proposals.set(&proposal_id, proposal);
+
+        self.env().emit_event(ProposalApproved { proposal_id, approver, approvals });
+    }
+
+    /// Execute a proposal that reached the approval threshold (owner or approver)
+    ///
+    /// Only approvals from current approvers count. Proposals can't be
+    /// executed while multisig is disabled, the owner acts alone then.
+    /// RemoveValidator, TransferOwnership, SetEmergencyTimeout,
+    /// SetProtocolFee and SetRebasingView are queued in the timelock
+    /// (eta = now + TIMELOCK_DELAY) and return the operation ID, executed
+    /// later by an ExecuteOperation proposal. Other actions apply
+    /// immediately and return 0.
+    pub fn execute_proposal(&mut self, proposal_id: u64) -> u64 {
+        let caller = self.env().caller();
+        if caller != self.ownable.get_owner() {
+            self.assert_approver(&caller);
+        }
+
+        let threshold = self.approval_threshold.get_or_default();
+        if threshold == 0 {
+            self.env().revert(Error::MultisigDisabled);
+        }
+        let mut proposal = self.pending_proposal_or_revert(proposal_id);
+        if self.approvals_from_current_approvers(proposal_id, &proposal) < threshold {
+            self.env().revert(Error::ThresholdNotReached);
+        }
+
+        proposal.executed = true;
+        self.proposals.set(&proposal_id, proposal.clone());
+        self.record_admin_activity();
+
+        let eta = self.env().get_block_time() + TIMELOCK_DELAY;
+        let mut operation_id = 0;
+        match proposal.kind {
+            ProposalKind::AdminDelegate => {
+                let validator = self.proposal_validator(&proposal);
+                self.delegate_pool_funds(validator, proposal.amount);
+            }
+            ProposalKind::AdminUndelegate => {
+                let validator = self.proposal_validator(&proposal);
+                self.undelegate_pool_funds(validator, proposal.amount);
+            }
+            ProposalKind::RemoveValidator => {
+                let validator = self.proposal_validator(&proposal);
+                operation_id = self.queue_operation(OperationKind::RemoveValidator, Some(validator), None, 0, eta);
+            }
+            ProposalKind::TransferOwnership => {
+                let new_owner = self.proposal_account(&proposal);
+                operation_id = self.queue_operation(OperationKind::TransferOwnership, None, Some(new_owner), 0, eta);
+            }
+            ProposalKind::AddApprover => {
+                let approver = self.proposal_account(&proposal);
+                self.register_approver(approver);
+            }
+            ProposalKind::RemoveApprover => {
+                let approver = self.proposal_account(&proposal);
+                self.unregister_approver(approver);
+            }
+            ProposalKind::SetApprovalThreshold => {
+                let threshold = if proposal.amount > U512::from(u8::MAX) {
+                    self.env().revert(Error::InvalidThreshold)
+                } else {
+                    proposal.amount.as_u32() as u8
+                };
+                self.update_approval_threshold(threshold);
+            }
+            ProposalKind::AdminProcessClaim => {
+                let request_id = if proposal.amount > U512::from(u64::MAX) {
+                    self.env().revert(Error::WithdrawalNotFound)
+                } else {
+                    proposal.amount.as_u64()
+                };
+                self.process_claim(request_id);
+            }
+            ProposalKind::AddValidator => {
+                let validator = self.proposal_validator(&proposal);
+                self.register_validator(validator);
+            }
+            ProposalKind::SetValidatorMaxAllocation => {
+                let validator = self.proposal_validator(&proposal);
+                self.update_validator_max_allocation(validator, proposal.amount);
+            }
+            ProposalKind::SetMaxValidatorShare => {
+                let bps = self.proposal_bps(&proposal);
+                self.update_max_validator_share(bps);
+            }
+            ProposalKind::ExecuteOperation => {
+                let queued_id = self.proposal_u64(&proposal);
+                self.run_operation(queued_id);
+            }
+            ProposalKind::CancelOperation => {
+                let queued_id = self.proposal_u64(&proposal);
+                self.drop_operation(queued_id);
+            }
+            ProposalKind::CreditPurseLiquidity => {
+                self.credit_purse_liquidity(proposal.amount);
+            }
+            ProposalKind::ReleaseLegacyReserve => {
+                self.release_legacy_reserve_to(self.ownable.get_owner());
+            }
+            ProposalKind::SetEmergencyTimeout => {
+                let timeout = self.proposal_u64(&proposal);
+                operation_id = self.queue_emergency_timeout(timeout, eta);
+            }
+            ProposalKind::SetProtocolFee => {
+                let bps = self.proposal_bps(&proposal);
+                operation_id = self.queue_protocol_fee(bps, eta);
+            }
+            ProposalKind::SetReferralFeeShare => {
+                let bps = self.proposal_bps(&proposal);
+                self.update_referral_fee_share(bps);
+            }
+            ProposalKind::SetRebasingView => {
+                let view = self.proposal_account(&proposal);
+                operation_id = self.queue_rebasing_view(view, eta);
+            }
+            ProposalKind::SetAllowlistEnabled => {
+                self.update_allowlist_enabled(!proposal.amount.is_zero());
+            }
+            ProposalKind::SetAllowlistManager => {
+                let manager = self.proposal_account(&proposal);
+                self.update_allowlist_manager(manager);
+            }
+            ProposalKind::SetTvlCap => {
+                self.update_tvl_cap(proposal.amount);
+            }
+            ProposalKind::SetMaxStakePerAccount => {
+                self.update_max_stake_per_account(proposal.amount);
+            }
+            ProposalKind::SetMinStakeAmount => {
+                self.update_min_stake_amount(proposal.amount);
+            }
+        }
+
+        self.env().emit_event(ProposalExecuted { proposal_id });
+        operation_id
+    }
+
+    fn register_approver(&mut self, approver: Address) {
+        if self.approvers.get(&approver).unwrap_or(false) {
+            self.env().revert(Error::ApproverAlreadyExists);
+        }
+        self.approvers.set(&approver, true);
+        self.approver_count.set(self.approver_count.get_or_default() + 1);
+        self.env().emit_event(ApproverAdded { approver });
+    }
+
+    fn unregister_approver(&mut self, approver: Address) {
+        if !self.approvers.get(&approver).unwrap_or(false) {
+            self.env().revert(Error::ApproverNotFound);
+        }
+        let count = self.approver_count.get_or_default() - 1;
+        if count < self.approval_threshold.get_or_default() {
+            self.env().revert(Error::InvalidThreshold);
+        }
+        self.approvers.set(&approver, false);
+        self.approver_count.set(count);
+        self.env().emit_event(ApproverRemoved { approver });
+    }
+
+    fn update_approval_threshold(&mut self, threshold: u8) {
+        if threshold > self.approver_count.get_or_default() {
+            self.env().revert(Error::InvalidThreshold);
+        }
+        self.approval_threshold.set(threshold);
+        self.env().emit_event(ApprovalThresholdUpdated { threshold });
+    }
+
+    fn pending_proposal_or_revert(&self, proposal_id: u64) -> Proposal {
+        let proposal = match self.proposals.get(&proposal_id) {
+            Some(proposal) => proposal,
+            None => self.env().revert(Error::ProposalNotFound),
+        };
+        if proposal.executed {
+            self.env().revert(Error::ProposalAlreadyExecuted);
+        }
+        proposal
+    }
+
+    fn proposal_validator(&self, proposal: &Proposal) -> PublicKey {
+        match &proposal.validator {
+            Some(validator) => validator.clone(),
+            None => self.env().revert(Error::NoValidatorSet),
+        }
+    }
+
+    fn proposal_account(&self, proposal: &Proposal) -> Address {
+        match proposal.account {
+            Some(account) => account,
+            None => self.env().revert(Error::NoAccountSet),
+        }
+    }
+
+    fn proposal_u64(&self, proposal: &Proposal) -> u64 {
+        if proposal.amount > U512::from(u64::MAX) {
+            self.env().revert(Error::ValueOverflow);
+        }
+        proposal.amount.as_u64()
+    }
+
+    fn proposal_bps(&self, proposal: &Proposal) -> u16 {
+        if proposal.amount > U512::from(BPS_DENOMINATOR) {
+            self.env().revert(Error::InvalidBasisPoints);
+        }
+        proposal.amount.as_u32() as u16
+    }
+
+    /// Approvals on a proposal from accounts that are still approvers
+    fn approvals_from_current_approvers(&self, proposal_id: u64, proposal: &Proposal) -> u8 {
+        let mut approvals = 0;
+        for index in 0..proposal.approvals {
+            if let Some(approver) = self.proposal_approver_at.get(&(proposal_id, index)) {
+                if self.approvers.get(&approver).unwrap_or(false) {
+                    approvals += 1;
+                }
+            }
+        }
+        approvals
+    }
+
+    fn assert_approver(&self, account: &Address) {
+        if !self.approvers.get(account).unwrap_or(false) {
+            self.env().revert(Error::NotApprover);
+        }
+    }
+
+    /// Owner check for actions that need proposals once multisig is enabled
+    fn assert_owner_acting_alone(&mut self) {
+        self.ownable.assert_owner(&self.env().caller());
+        if self.approval_threshold.get_or_default() > 0 {
+            self.env().revert(Error::ApprovalRequired);
+        }
+        self.record_admin_activity();
+    }
+
+    fn queued_operation_or_revert(&self, operation_id: u64) -> TimelockOperation {
+        let operation = match self.timelock_operations.get(&operation_id) {
+            Some(operation) => operation,
+            None => self.env().revert(Error::OperationNotFound),
+        };
+        if operation.status != OperationStatus::Queued {
+            self.env().revert(Error::OperationNotQueued);
+        }
+        operation
+    }
+
+    // ========================================================================
+    // VIEW FUNCTIONS
+    // ========================================================================
+
+    /// Get stCSPR balance of an address
+    pub fn get_stcspr_balance(&self, account: Address) -> U256 {
+        self.token.balance_of(&account)
+    }
+
+    /// Get CSPR value of stCSPR balance
+    pub fn get_cspr_value(&self, account: Address) -> U512 {
+        let stcspr_balance = self.token.balance_of(&account);
+        self.stcspr_to_cspr(stcspr_balance)
+    }
+
+    /// Get total CSPR in pool
+    pub fn get_total_pool(&self) -> U512 {
+        self.total_cspr_pool.get_or_default()
+    }
+
+    /// Get total pending withdrawals
+    pub fn get_pending_withdrawals(&self) -> U512 {
+        self.pending_withdrawals.get_or_default()
+    }
+
+    /// Get available liquidity (V20)
+    /// This is CSPR in the contract that can be used for claims
+    pub fn get_available_liquidity(&self) -> U512 {
+        self.available_liquidity.get_or_default()
+    }
+
+    /// Get pending undelegations (V20)
+    /// Amount that needs to be undelegated by admin
+    pub fn get_pending_undelegations(&self) -> U512 {
+        self.pending_undelegations.get_or_default()
+    }
+
+    /// Get storage layout version of the deployed contract
+    pub fn get_contract_version(&self) -> u32 {
+        self.contract_version.get().unwrap_or(LEGACY_CONTRACT_VERSION)
+    }
+
+    /// Check if emergency mode is active
+    pub fn is_emergency_mode(&self) -> bool {
+        self.emergency_mode.get_or_default()
+    }
+
+    /// Get CSPR emergency_undelegate() undelegated for a request
+    pub fn get_emergency_undelegated(&self, request_id: u64) -> U512 {
+        self.emergency_undelegated.get(&request_id).unwrap_or_default()
+    }
+
+    /// Get emergency-undelegated CSPR not claimed yet
+    pub fn get_emergency_unbonding(&self) -> U512 {
+        self.emergency_unbonding.get_or_default()
+    }
+
+    /// Get block time of the last owner action
+    pub fn get_last_admin_activity(&self) -> u64 {
+        self.last_admin_activity.get_or_default()
+    }
+
+    /// Get inactivity period before emergency mode can be triggered
+    pub fn get_emergency_timeout(&self) -> u64 {
+        self.emergency_timeout.get_or_default()
+    }
+
+    /// Get a timelocked operation by ID
+    pub fn get_operation(&self, operation_id: u64) -> Option<TimelockOperation> {
+        self.timelock_operations.get(&operation_id)
+    }
+
+    /// Get next timelock operation ID (for iterating the queue)
+    pub fn get_next_operation_id(&self) -> u64 {
+        self.next_operation_id.get_or_default()
+    }
+
+    /// Get minimum delay between queueing and executing an operation
+    pub fn get_timelock_delay(&self) -> u64 {
+        TIMELOCK_DELAY
+    }
+
+    /// Get a multisig proposal by ID
+    pub fn get_proposal(&self, proposal_id: u64) -> Option<Proposal> {
+        self.proposals.get(&proposal_id)
+    }
+
+    /// Check if a proposal has enough approvals from current approvers
+    /// and is not executed yet (always false while multisig is disabled)
+    pub fn is_proposal_approved(&self, proposal_id: u64) -> bool {
+        let threshold = self.approval_threshold.get_or_default();
+        match self.proposals.get(&proposal_id) {
+            Some(proposal) => {
+                threshold > 0 &&
+                !proposal.executed &&
+                self.approvals_from_current_approvers(proposal_id, &proposal) >= threshold
+            }
+            None => false,
+        }
+    }
+
+    /// Check if an approver has approved a proposal
+    pub fn has_approved(&self, proposal_id: u64, approver: Address) -> bool {
+        self.proposal_approvals.get(&(proposal_id, approver)).unwrap_or(false)
+    }
+
+    /// Get next proposal ID (for iterating proposals)
+    pub fn get_next_proposal_id(&self) -> u64 {
+        self.next_proposal_id.get_or_default()
+    }
+
+    /// Check if an address is a registered approver
+    pub fn is_approver(&self, account: Address) -> bool {
+        self.approvers.get(&account).unwrap_or(false)
+    }
+
+    /// Get number of registered approvers
+    pub fn get_approver_count(&self) -> u8 {
+        self.approver_count.get_or_default()
+    }
+
+    /// Get approvals required per proposal (0 = multisig disabled)
+    pub fn get_approval_threshold(&self) -> u8 {
+        self.approval_threshold.get_or_default()
+    }
+
+    /// Get global TVL cap (0 = no cap)
+    pub fn get_tvl_cap(&self) -> U512 {
+        self.tvl_cap.get_or_default()
+    }
+
+    /// Get max CSPR value per account (0 = no limit)
+    pub fn get_max_stake_per_account(&self) -> U512 {
+        self.max_stake_per_account.get_or_default()
+    }
+
+    /// Get minimum CSPR per stake call (0 = any amount)
+    pub fn get_min_stake_amount(&self) -> U512 {
+        self.min_stake_amount.get_or_default()
+    }
+
+    /// Get CSPR that can still be staked before hitting the TVL cap
+    /// (U512::MAX if uncapped)
+    pub fn get_remaining_capacity(&self) -> U512 {
+        let tvl_cap = self.tvl_cap.get_or_default();
+        if tvl_cap == U512::zero() {
+            return U512::MAX;
+        }
+        tvl_cap.saturating_sub(self.total_cspr_pool.get_or_default())
+    }
+
+    /// Get CSPR an account can still stake, combining the TVL cap and the
+    /// per-account limit (U512::MAX if both disabled)
+    pub fn get_remaining_account_capacity(&self, account: Address) -> U512 {
+        self.get_remaining_capacity().min(self.account_headroom(&account))
+    }
+
+    /// Check if allowlist mode is enabled
+    pub fn is_allowlist_enabled(&self) -> bool {
+        self.allowlist_enabled.get_or_default()
+    }
+
+    /// Check if an account is allowlisted
+    pub fn is_allowlisted(&self, account: Address) -> bool {
+        self.allowlisted.get(&account).unwrap_or(false)
+    }
+
+    /// Get allowlist manager (None if only the owner manages it)
+    pub fn get_allowlist_manager(&self) -> Option<Address> {
+        self.allowlist_manager.get()
+    }
+
+    /// Get protocol fee on harvested rewards (bps)
+    pub fn get_protocol_fee_bps(&self) -> u16 {
+        self.protocol_fee_bps.get_or_default()
+    }
+
+    /// Get share of the protocol fee paid to referrers (bps of the fee)
+    pub fn get_referral_fee_share_bps(&self) -> u16 {
+        self.referral_fee_share_bps.get_or_default()
+    }
+
+    /// Get a staker's referrer
+    pub fn get_referrer(&self, staker: Address) -> Option<Address> {
+        self.staker_referrer.get(&staker)
+    }
+
+    /// Get active CSPR TVL brought in by a referrer
+    pub fn get_referred_tvl(&self, referrer: Address) -> U512 {
+        self.referred_tvl.get(&referrer).unwrap_or_default()
+    }
+
+    /// Get number of stakers a referrer brought in
+    pub fn get_referral_count(&self, referrer: Address) -> u64 {
+        self.referral_count.get(&referrer).unwrap_or(0)
+    }
+
+    /// Get claimable referral rewards in stCSPR
+    pub fn get_pending_referral_rewards(&self, referrer: Address) -> U256 {
+        self.pending_referral_rewards(&referrer)
+    }
+
+    /// Get contract owner
+    pub fn get_owner(&self) -> Address {
+        self.ownable.get_owner()
+    }
+
+    /// Get number of validators
+    pub fn get_validator_count(&self) -> u8 {
+        self.validator_count.get_or_default()
+    }
+
+    /// Get validator by index
+    pub fn get_validator(&self, index: u8) -> Option<PublicKey> {
+        self.validators.get(&index)
+    }
+
+    /// Check if validator is approved
+    pub fn is_validator_active(&self, validator: PublicKey) -> bool {
+        self.validator_active.get(&validator).unwrap_or(false)
+    }
+
+    /// Get validator metadata (None if never added)
+    pub fn get_validator_info(&self, validator: PublicKey) -> Option<ValidatorInfo> {
+        self.validator_info.get(&validator)
+    }
+
+    /// Get max share of total pool per validator (bps, 0 = no limit)
+    pub fn get_max_validator_share_bps(&self) -> u16 {
+        self.max_validator_share_bps.get_or_default()
+    }
+
+    /// Get how much more can be delegated to a validator before hitting
+    /// its absolute cap or the concentration limit (U512::MAX if unlimited)
+    pub fn get_validator_headroom(&self, validator: PublicKey) -> U512 {
+        self.cap_headroom(&validator).min(self.share_headroom(&validator))
+    }
+
+    /// Get CSPR in the contract purse not accounted to pool liquidity, the
+    /// legacy reserve or emergency unbonding (creditable as liquidity)
+    pub fn get_purse_surplus(&self) -> U512 {
+        let held = self.available_liquidity.get_or_default()
+            + self.legacy_reserve.get_or_default()
+            + self.emergency_unbonding.get_or_default();
+        self.env().self_balance().saturating_sub(held)
+    }
+
+    /// Get amount delegated to a validator
+    pub fn get_delegated_to_validator(&self, validator: PublicKey) -> U512 {
+        self.validator_delegated.get(&validator).unwrap_or(U512::zero())
+    }
+
+    /// Get withdrawal request CSPR amount by ID
+    pub fn get_withdrawal_amount(&self, request_id: u64) -> U512 {
+        match self.withdrawal_requests.get(&request_id) {
+            Some(request) => request.cspr_amount,
+            None => U512::zero(),
+        }
+    }
+
+    /// Get withdrawal request staker by ID
+    pub fn get_withdrawal_staker(&self, request_id: u64) -> Option<Address> {
+        self.withdrawal_requests.get(&request_id).map(|r| r.staker)
+    }
+
+    /// Get withdrawal payout address by ID
+    pub fn get_withdrawal_receiver(&self, request_id: u64) -> Option<Address> {
+        match self.withdrawal_receiver.get(&request_id) {
+            Some(receiver) => Some(receiver),
+            None => self.withdrawal_requests.get(&request_id).map(|r| r.staker),
+        }
+    }
+
+    /// Check if withdrawal is claimed
+    pub fn is_withdrawal_claimed(&self, request_id: u64) -> bool {
+        match self.withdrawal_requests.get(&request_id) {
+            Some(request) => request.claimed,
+            None => false,
+        }
+    }
+
+    /// Get number of withdrawal requests for a user
+    pub fn get_user_request_count(&self, user: Address) -> u64 {
+        self.user_request_count.get(&user).unwrap_or(0)
+    }
+
+    /// Get user's withdrawal request ID by index
+    pub fn get_user_request_id(&self, user: Address, index: u64) -> Option<u64> {
+        self.user_requests.get(&(user, index))
+    }
+
+    /// Check if withdrawal is ready to claim
+    pub fn is_withdrawal_ready(&self, request_id: u64) -> bool {
+        match self.withdrawal_requests.get(&request_id) {
+            Some(request) => {
+                !request.claimed &&
+                self.env().get_block_time() >= request.request_block + UNBONDING_BLOCKS
+            }
+            None => false,
+        }
+    }
+
+    /// Get token info
+    pub fn token_name(&self) -> String {
+        self.token.name()
+    }
+
+    pub fn token_symbol(&self) -> String {
+        self.token.symbol()
+    }
+
+    pub fn token_total_supply(&self) -> U256 {
+        self.token.total_supply()
+    }
+
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+fn u512_to_u256(value: U512) -> U256 {
+    let mut bytes = [0u8; 64];
+    value.to_little_endian(&mut bytes);
+    // Check for overflow - upper 32 bytes must be zero
+    for i in 32..64 {
+        if bytes[i] != 0 {
+            // Value too large for U256 - this should never happen with normal token amounts
+            // but we handle it gracefully by returning max U256
+            return U256::MAX;
+        }
+    }
+    U256::from_little_endian(&bytes[..32])
+}
+
+fn u256_to_u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rebasing::{RebasingStCspr, RebasingStCsprInitArgs};
+    use odra::host::{Deployer, HostRef, InstallConfig, NoArgs};
+    use odra::casper_types::AsymmetricType;
+
+    // Valid Ed25519 test public keys (32 bytes)
+    fn test_validator() -> PublicKey {
+        // Use a valid 32-byte Ed25519 public key
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0x01;
+        bytes[31] = 0x01;
+        PublicKey::ed25519_from_bytes(bytes).unwrap()
+    }
+
+    fn test_validator2() -> PublicKey {
+        // Second valid 32-byte Ed25519 public key
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0x02;
+        bytes[31] = 0x02;
+        PublicKey::ed25519_from_bytes(bytes).unwrap()
+    }
+
+    fn unapproved_validator() -> PublicKey {
+        // Third valid 32-byte Ed25519 public key (not approved)
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0x03;
+        bytes[31] = 0x03;
+        PublicKey::ed25519_from_bytes(bytes).unwrap()
+    }
+
+    fn setup() -> (odra::host::HostEnv, StakeVueHostRef) {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let mut contract = StakeVue::deploy(&env, StakeVueInitArgs { owner });
+
+        // Add test validator
+        env.set_caller(owner);
+        contract.add_validator(test_validator());
+
+        (env, contract)
+    }
+
+    #[test]
+    fn test_initial_state() {
+        let (_env, contract) = setup();
+        assert_eq!(contract.get_contract_version(), CONTRACT_VERSION);
+        assert_eq!(contract.get_exchange_rate(), U512::from(RATE_PRECISION));
+        assert_eq!(contract.get_validator_count(), 1);
+        assert!(contract.is_validator_active(test_validator()));
+    }
+
+    #[test]
+    fn test_add_multiple_validators() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        env.set_caller(owner);
+
+        contract.add_validator(test_validator2());
+
+        assert_eq!(contract.get_validator_count(), 2);
+        assert!(contract.is_validator_active(test_validator()));
+        assert!(contract.is_validator_active(test_validator2()));
+    }
+
+    #[test]
+    fn test_stake_to_validator() {
+        let (env, mut contract) = setup();
+        let staker = env.get_account(1);
+        env.set_caller(staker);
+
+        let stake_amount = U512::from(MIN_DELEGATION);
+        contract.with_tokens(stake_amount).stake(test_validator());
+
+        // V20: stake adds to pool and available_liquidity (not validator_delegated)
+        assert_eq!(contract.get_stcspr_balance(staker), U256::from(MIN_DELEGATION));
+        assert_eq!(contract.get_total_pool(), stake_amount);
+        assert_eq!(contract.get_available_liquidity(), stake_amount);
+        // Validator delegated is 0 until admin calls admin_delegate
+        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::zero());
+    }
+
+    #[test]
+    fn test_stake_to_multiple_validators() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        // Add second validator
+        env.set_caller(owner);
+        contract.add_validator(test_validator2());
+
+        // Stake (validator param is just for routing preference in V20)
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator2());
+
+        // V20: all CSPR goes to available_liquidity
+        assert_eq!(contract.get_stcspr_balance(staker), U256::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_available_liquidity(), U512::from(MIN_DELEGATION * 2));
+    }
+
+    #[test]
+    fn test_admin_delegate() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        // User stakes
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+
+        // Admin delegates to validator
+        env.set_caller(owner);
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+
+        // Now validator has delegation, liquidity is 0
+        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_available_liquidity(), U512::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "ValidatorNotApproved")]
+    fn test_stake_to_unapproved_validator_fails() {
+        let (env, contract) = setup();
+        env.set_caller(env.get_account(1));
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(unapproved_validator());
+    }
+
+    #[test]
+    fn test_request_unstake() {
+        let (env, mut contract) = setup();
+        let staker = env.get_account(1);
+        env.set_caller(staker);
+
+        // Stake first
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+
+        // Request unstake (V20: no validator param needed)
+        let request_id = contract.request_unstake(U512::from(MIN_DELEGATION));
+
+        assert_eq!(request_id, 1);
+        assert_eq!(contract.get_stcspr_balance(staker), U256::zero());
+        assert_eq!(contract.get_pending_withdrawals(), U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_pending_undelegations(), U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_user_request_count(staker), 1);
+    }
+
+    #[test]
+    fn test_admin_undelegate() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        // User stakes
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+
+        // Admin delegates
+        env.set_caller(owner);
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+
+        // User requests unstake
+        env.set_caller(staker);
+        contract.request_unstake(U512::from(MIN_DELEGATION));
+
+        // Admin undelegates
+        env.set_caller(owner);
+        contract.admin_undelegate(test_validator(), U512::from(MIN_DELEGATION));
+
+        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::zero());
+        assert_eq!(contract.get_pending_undelegations(), U512::zero());
+    }
+
+    #[test]
+    fn test_admin_credit_purse_liquidity() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(MIN_DELEGATION * 2)).stake(test_validator());
+        env.set_caller(owner);
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION * 2));
+        env.set_caller(staker);
+        contract.request_unstake(U512::from(MIN_DELEGATION * 2));
+        env.set_caller(owner);
+        contract.admin_undelegate(test_validator(), U512::from(MIN_DELEGATION * 2));
+
+        // Test auction calls are compiled out, so the "payout" is already in the purse
+        assert_eq!(contract.get_purse_surplus(), U512::from(MIN_DELEGATION * 2));
+
+        env.set_caller(staker);
+        assert!(contract.try_admin_credit_purse_liquidity(U512::from(MIN_DELEGATION)).is_err());
+
+        env.set_caller(owner);
+        let result = contract.try_admin_credit_purse_liquidity(U512::from(MIN_DELEGATION * 2 + 1));
+        assert_eq!(result, Err(Error::InsufficientLiquidity.into()));
+
+        contract.admin_credit_purse_liquidity(U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_available_liquidity(), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_purse_surplus(), U512::zero());
+        let result = contract.try_admin_credit_purse_liquidity(U512::one());
+        assert_eq!(result, Err(Error::InsufficientLiquidity.into()));
+    }
+
+    #[test]
+    fn test_harvest_rewards() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        // Stake
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(1000_000_000_000u64)).stake(test_validator());
+
+        // Harvest rewards
+        env.set_caller(owner);
+        contract.with_tokens(U512::from(100_000_000_000u64)).harvest_rewards();
+
+        // Exchange rate should increase
+        assert_eq!(contract.get_total_pool(), U512::from(1100_000_000_000u64));
+        // Rate = 1100/1000 * 1e9 = 1.1e9
+        assert_eq!(contract.get_exchange_rate(), U512::from(1_100_000_000u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "BelowMinimumDelegation")]
+    fn test_admin_delegate_below_minimum_fails() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        // User stakes (any amount is ok in V20)
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(100_000_000_000u64)).stake(test_validator());
+
+        // Admin tries to delegate below minimum - should fail
+        env.set_caller(owner);
+        contract.admin_delegate(test_validator(), U512::from(100_000_000_000u64));
+    }
+
+    #[test]
+    fn test_stake_any_amount_succeeds() {
+        let (env, mut contract) = setup();
+        let staker = env.get_account(1);
+        env.set_caller(staker);
+
+        // V20: stake accepts any amount (minimum is checked on admin_delegate)
+        contract.with_tokens(U512::from(100_000_000_000u64)).stake(test_validator());
+        contract.with_tokens(U512::from(100_000_000_000u64)).stake(test_validator());
+
+        assert_eq!(contract.get_stcspr_balance(staker), U256::from(200_000_000_000u64));
+        assert_eq!(contract.get_available_liquidity(), U512::from(200_000_000_000u64));
+    }
+
+    #[test]
+    fn test_remove_validator() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        env.set_caller(owner);
+
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let operation_id = contract.queue_remove_validator(test_validator(), eta);
+        assert!(contract.is_validator_active(test_validator()));
+
+        env.advance_block_time(TIMELOCK_DELAY);
+        contract.execute_operation(operation_id);
+        assert!(!contract.is_validator_active(test_validator()));
+        assert_eq!(contract.get_operation(operation_id).unwrap().status, OperationStatus::Executed);
+    }
+
+    #[test]
+    fn test_timelock_transfer_ownership() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let new_owner = env.get_account(1);
+        env.set_caller(owner);
+
+        let result = contract.try_queue_transfer_ownership(new_owner, env.block_time() + TIMELOCK_DELAY - 1);
+        assert_eq!(result, Err(Error::TimelockEtaTooSoon.into()));
+
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let operation_id = contract.queue_transfer_ownership(new_owner, eta);
+        assert!(env.emitted_event(
+            &contract,
+            OperationQueued { operation_id, kind: OperationKind::TransferOwnership, eta }
+        ));
+
+        let result = contract.try_execute_operation(operation_id);
+        assert_eq!(result, Err(Error::OperationNotReady.into()));
+
+        env.advance_block_time(TIMELOCK_DELAY);
+        contract.execute_operation(operation_id);
+        assert_eq!(contract.get_owner(), new_owner);
+    }
+
+    #[test]
+    fn test_timelock_cancel_and_expiry() {
+        let (env, mut contract) = setup();
+        env.set_caller(env.get_account(0));
+
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let cancelled = contract.queue_remove_validator(test_validator(), eta);
+        let expired = contract.queue_set_emergency_timeout(1_000, eta);
+
+        contract.cancel_operation(cancelled);
+        env.advance_block_time(TIMELOCK_DELAY);
+        let result = contract.try_execute_operation(cancelled);
+        assert_eq!(result, Err(Error::OperationNotQueued.into()));
+
+        env.advance_block_time(TIMELOCK_GRACE_PERIOD + 1);
+        let result = contract.try_execute_operation(expired);
+        assert_eq!(result, Err(Error::OperationExpired.into()));
+        assert_eq!(contract.get_emergency_timeout(), DEFAULT_EMERGENCY_TIMEOUT);
+    }
+
+    #[test]
+    fn test_validator_info() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        env.set_caller(owner);
+
+        let info = contract.get_validator_info(test_validator()).unwrap();
+        assert_eq!(info.name, String::new());
+        assert_eq!(info.max_allocation, U512::zero());
+
+        contract.set_validator_name(test_validator(), String::from("Make"));
+        contract.set_validator_commission(test_validator(), 5);
+        contract.set_validator_max_allocation(test_validator(), U512::from(MIN_DELEGATION * 10));
+        contract.set_validator_notes(test_validator(), String::from("testnet"));
+
+        let info = contract.get_validator_info(test_validator()).unwrap();
+        assert_eq!(info.name, String::from("Make"));
+        assert_eq!(info.commission_rate, 5);
+        assert_eq!(info.max_allocation, U512::from(MIN_DELEGATION * 10));
+        assert_eq!(info.notes, String::from("testnet"));
+        assert!(contract.get_validator_info(unapproved_validator()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidCommissionRate")]
+    fn test_set_validator_commission_above_100_fails() {
+        let (env, mut contract) = setup();
+        env.set_caller(env.get_account(0));
+        contract.set_validator_commission(test_validator(), 101);
+    }
+
+    #[test]
+    #[should_panic(expected = "ValidatorCapExceeded")]
+    fn test_admin_delegate_above_validator_cap_fails() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+
+        env.set_caller(env.get_account(1));
+        contract.with_tokens(U512::from(MIN_DELEGATION * 3)).stake(test_validator());
+
+        env.set_caller(owner);
+        contract.set_validator_max_allocation(test_validator(), U512::from(MIN_DELEGATION * 2));
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_validator_headroom(test_validator()), U512::zero());
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+    }
+
+    #[test]
+    fn test_concentration_limit() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+
+        env.set_caller(env.get_account(1));
+        contract.with_tokens(U512::from(MIN_DELEGATION * 4)).stake(test_validator());
+
+        // 50% of a 2000 CSPR pool = 1000 CSPR per validator
+        env.set_caller(owner);
+        assert_eq!(contract.get_validator_headroom(test_validator()), U512::MAX);
+        contract.set_max_validator_share_bps(5_000);
+        assert_eq!(contract.get_validator_headroom(test_validator()), U512::from(MIN_DELEGATION * 2));
+
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_validator_headroom(test_validator()), U512::from(MIN_DELEGATION));
+
+        let result = contract.try_admin_delegate(test_validator(), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(result, Err(Error::ConcentrationLimitExceeded.into()));
+    }
+
+    #[test]
+    fn test_claim_migrated_stcspr() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let alice = env.get_account(1);
+        let bob = env.get_account(2);
+
+        // Two-leaf snapshot: root = H(sorted(leaf_alice, leaf_bob))
+        let alice_amount = U512::from(MIN_DELEGATION * 2);
+        let bob_amount = U512::from(MIN_DELEGATION);
+        let leaf_alice = contract.get_legacy_leaf(alice, alice_amount);
+        let leaf_bob = contract.get_legacy_leaf(bob, bob_amount);
+        let root = hash_pair(leaf_alice, leaf_bob);
+
+        env.set_caller(owner);
+        contract.with_tokens(alice_amount + bob_amount).import_legacy_positions(root);
+        assert_eq!(contract.get_legacy_merkle_root(), Some(root));
+        // Reserve is held aside, rate and pool untouched
+        assert_eq!(contract.get_total_pool(), U512::zero());
+
+        env.set_caller(alice);
+        let minted = contract.claim_migrated_stcspr(alice_amount, vec![leaf_bob]);
+        assert_eq!(minted, U256::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_stcspr_balance(alice), minted);
+        assert_eq!(contract.get_total_pool(), alice_amount);
+        assert_eq!(contract.get_available_liquidity(), alice_amount);
+        assert_eq!(contract.get_legacy_reserve(), bob_amount);
+        assert!(contract.is_legacy_claimed(alice));
+
+        // Second claim and forged amounts are rejected
+        let result = contract.try_claim_migrated_stcspr(alice_amount, vec![leaf_bob]);
+        assert_eq!(result, Err(Error::AlreadyMigrated.into()));
+
+        env.set_caller(bob);
+        let result = contract.try_claim_migrated_stcspr(bob_amount * 2, vec![leaf_alice]);
+        assert_eq!(result, Err(Error::InvalidMerkleProof.into()));
+        contract.claim_migrated_stcspr(bob_amount, vec![leaf_alice]);
+        assert_eq!(contract.get_legacy_reserve(), U512::zero());
+    }
+
+    #[test]
+    fn test_release_legacy_reserve_after_claim_window() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let alice = env.get_account(1);
+        let bob = env.get_account(2);
+
+        let alice_amount = U512::from(MIN_DELEGATION * 2);
+        let bob_amount = U512::from(MIN_DELEGATION);
+        let leaf_alice = contract.get_legacy_leaf(alice, alice_amount);
+        let leaf_bob = contract.get_legacy_leaf(bob, bob_amount);
+
+        env.set_caller(owner);
+        contract.with_tokens(alice_amount + bob_amount).import_legacy_positions(hash_pair(leaf_alice, leaf_bob));
+        assert_eq!(contract.get_legacy_claim_deadline(), env.block_time() + LEGACY_CLAIM_WINDOW);
+        let result = contract.try_release_legacy_reserve();
+        assert_eq!(result, Err(Error::LegacyClaimWindowOpen.into()));
+
+        env.set_caller(alice);
+        contract.claim_migrated_stcspr(alice_amount, vec![leaf_bob]);
+
+        // Bob never claims: the owner gets his CSPR back, and he can't claim any more
+        env.advance_block_time(LEGACY_CLAIM_WINDOW + 1);
+        env.set_caller(bob);
+        assert!(contract.try_release_legacy_reserve().is_err());
+        let result = contract.try_claim_migrated_stcspr(bob_amount, vec![leaf_alice]);
+        assert_eq!(result, Err(Error::LegacyClaimWindowClosed.into()));
+
+        env.set_caller(owner);
+        let purse = env.balance_of(contract.address());
+        assert_eq!(contract.release_legacy_reserve(), bob_amount);
+        assert_eq!(env.balance_of(contract.address()), purse - bob_amount);
+        assert_eq!(contract.get_legacy_reserve(), U512::zero());
+        assert_eq!(contract.get_available_liquidity(), alice_amount);
+        assert!(env.emitted_event(&contract, LegacyReserveReleased { recipient: owner, cspr_amount: bob_amount }));
+    }
+
+    #[test]
+    #[should_panic(expected = "LegacyImportAlreadySet")]
+    fn test_import_legacy_positions_only_once() {
+        let (env, mut contract) = setup();
+        env.set_caller(env.get_account(0));
+        contract.with_tokens(U512::from(MIN_DELEGATION)).import_legacy_positions([1u8; 32]);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).import_legacy_positions([2u8; 32]);
+    }
+
+    #[test]
+    fn test_emergency_mode_exit() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        env.set_caller(owner);
+        contract.add_validator(test_validator2());
+
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(MIN_DELEGATION * 4)).stake(test_validator());
+
+        env.set_caller(owner);
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION * 3));
+        contract.admin_delegate(test_validator2(), U512::from(MIN_DELEGATION));
+
+        env.set_caller(staker);
+        let request_id = contract.request_unstake(U512::from(MIN_DELEGATION * 2));
+
+        // Owner still considered active
+        let result = contract.try_trigger_emergency_mode();
+        assert_eq!(result, Err(Error::AdminStillActive.into()));
+
+        // Owner disappears, anyone can trigger
+        env.advance_block_time(DEFAULT_EMERGENCY_TIMEOUT);
+        env.set_caller(env.get_account(2));
+        contract.trigger_emergency_mode();
+        assert!(contract.is_emergency_mode());
+
+        // Staking is frozen
+        let result = contract.with_tokens(U512::from(MIN_DELEGATION)).try_stake(test_validator());
+        assert_eq!(result, Err(Error::EmergencyModeActive.into()));
+
+        // Staker undelegates 1000 CSPR pro-rata (750 / 250)
+        env.set_caller(staker);
+        contract.emergency_undelegate(request_id);
+        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::from(MIN_DELEGATION * 3 / 2));
+        assert_eq!(contract.get_delegated_to_validator(test_validator2()), U512::from(MIN_DELEGATION / 2));
+        assert_eq!(contract.get_pending_undelegations(), U512::zero());
+        assert_eq!(contract.get_emergency_undelegated(request_id), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_emergency_unbonding(), U512::from(MIN_DELEGATION * 2));
+
+        let result = contract.try_emergency_claim(request_id);
+        assert_eq!(result, Err(Error::WithdrawalNotReady.into()));
+
+        env.advance_block_time(UNBONDING_BLOCKS);
+        contract.emergency_claim(request_id);
+        assert!(contract.is_withdrawal_claimed(request_id));
+        assert_eq!(contract.get_pending_withdrawals(), U512::zero());
+        assert_eq!(contract.get_emergency_unbonding(), U512::zero());
+    }
+
+    #[test]
+    fn test_emergency_undelegate_capped_at_pending_undelegations() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let staker = env.get_account(1);
+
+        env.set_caller(staker);
+        contract.with_tokens(U512::from(MIN_DELEGATION * 4)).stake(test_validator());
+        env.set_caller(owner);
+        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION * 4));
+
+        // Owner undelegates 300 of the 1000 CSPR request before disappearing
+        env.set_caller(staker);
+        let request_id = contract.request_unstake(U512::from(MIN_DELEGATION * 2));
+        env.set_caller(owner);
+        contract.admin_undelegate(test_validator(), U512::from(300_000_000_000u64));
+
+        env.advance_block_time(DEFAULT_EMERGENCY_TIMEOUT);
+        contract.trigger_emergency_mode();
+
+        // Only the remaining 700 CSPR is undelegated again
+        env.set_caller(staker);
+        contract.emergency_undelegate(request_id);
+        assert_eq!(contract.get_emergency_undelegated(request_id), U512::from(700_000_000_000u64));
+        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_pending_undelegations(), U512::zero());
+
+        // The owner's 300 CSPR must come through available_liquidity
+        env.advance_block_time(UNBONDING_BLOCKS);
+        let result = contract.try_emergency_claim(request_id);
+        assert_eq!(result, Err(Error::InsufficientLiquidity.into()));
+
+        env.set_caller(owner);
+        contract.with_tokens(U512::from(300_000_000_000u64)).admin_add_liquidity();
+        env.set_caller(staker);
+        let purse = env.balance_of(contract.address());
+        contract.emergency_claim(request_id);
+        assert_eq!(env.balance_of(contract.address()), purse - U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_available_liquidity(), U512::zero());
+        assert_eq!(contract.get_emergency_unbonding(), U512::zero());
+    }
+
+    #[test]
+    fn test_admin_activity_resets_emergency_timer() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+
+        env.advance_block_time(DEFAULT_EMERGENCY_TIMEOUT - 1);
+        env.set_caller(owner);
+        contract.heartbeat();
+
+        env.advance_block_time(DEFAULT_EMERGENCY_TIMEOUT - 1);
+        env.set_caller(env.get_account(1));
+        let result = contract.try_trigger_emergency_mode();
+        assert_eq!(result, Err(Error::AdminStillActive.into()));
+    }
+
+    #[test]
+    fn test_multisig_admin_delegate() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let approver1 = env.get_account(1);
+        let approver2 = env.get_account(2);
+        let approver3 = env.get_account(3);
+
+        env.set_caller(env.get_account(4));
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
 
-    pub fn token_symbol(&self) -> String {
-        self.token.symbol()
-    }
+        // 2-of-3
+        env.set_caller(owner);
+        contract.add_approver(approver1);
+        contract.add_approver(approver2);
+        contract.add_approver(approver3);
+        contract.set_approval_threshold(2);
+
+        // Owner can no longer act alone
+        let result = contract.try_admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+        assert_eq!(result, Err(Error::ApprovalRequired.into()));
+
+        env.set_caller(approver1);
+        let proposal_id = contract.create_proposal(
+            ProposalKind::AdminDelegate,
+            Some(test_validator()),
+            None,
+            U512::from(MIN_DELEGATION),
+        );
+        assert!(!contract.is_proposal_approved(proposal_id));
 
-    pub fn token_total_supply(&self) -> U256 {
-        self.token.total_supply()
-    }
+        env.set_caller(owner);
+        let result = contract.try_execute_proposal(proposal_id);
+        assert_eq!(result, Err(Error::ThresholdNotReached.into()));
 
-}
+        env.set_caller(approver2);
+        contract.approve_proposal(proposal_id);
+        assert!(contract.has_approved(proposal_id, approver2));
+        assert!(contract.is_proposal_approved(proposal_id));
 
-// ============================================================================
-// HELPERS
-// ============================================================================
+        env.set_caller(owner);
+        contract.execute_proposal(proposal_id);
+        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::from(MIN_DELEGATION));
+        assert!(contract.get_proposal(proposal_id).unwrap().executed);
 
-fn u512_to_u256(value: U512) -> U256 {
-    let mut bytes = [0u8; 64];
-    value.to_little_endian(&mut bytes);
-    // Check for overflow - upper 32 bytes must be zero
-    for i in 32..64 {
-        if bytes[i] != 0 {
-            // Value too large for U256 - this should never happen with normal token amounts
-            // but we handle it gracefully by returning max U256
-            return U256::MAX;
-        }
+        let result = contract.try_execute_proposal(proposal_id);
+        assert_eq!(result, Err(Error::ProposalAlreadyExecuted.into()));
     }
-    U256::from_little_endian(&bytes[..32])
-}
 
-fn u256_to_u512(value: U256) -> U512 {
-    let mut bytes = [0u8; 32];
-    value.to_little_endian(&mut bytes);
-    U512::from_little_endian(&bytes)
-}
+    #[test]
+    fn test_multisig_transfer_ownership_goes_through_timelock() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let approver = env.get_account(1);
+        let new_owner = env.get_account(2);
 
-// ============================================================================
-// TESTS
-// ============================================================================
+        env.set_caller(owner);
+        contract.add_approver(approver);
+        contract.set_approval_threshold(1);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use odra::host::{Deployer, HostRef};
-    use odra::casper_types::AsymmetricType;
+        env.set_caller(approver);
+        let proposal_id = contract.create_proposal(ProposalKind::TransferOwnership, None, Some(new_owner), U512::zero());
+        let operation_id = contract.execute_proposal(proposal_id);
+        assert_eq!(contract.get_owner(), owner);
 
-    // Valid Ed25519 test public keys (32 bytes)
-    fn test_validator() -> PublicKey {
-        // Use a valid 32-byte Ed25519 public key
-        let mut bytes = [0u8; 32];
-        bytes[0] = 0x01;
-        bytes[31] = 0x01;
-        PublicKey::ed25519_from_bytes(bytes).unwrap()
+        // The owner can't run the queued operation alone, approvers do
+        env.set_caller(owner);
+        env.advance_block_time(TIMELOCK_DELAY);
+        assert_eq!(contract.try_execute_operation(operation_id), Err(Error::ApprovalRequired.into()));
+        env.set_caller(approver);
+        let proposal_id = contract.create_proposal(ProposalKind::ExecuteOperation, None, None, U512::from(operation_id));
+        contract.execute_proposal(proposal_id);
+        assert_eq!(contract.get_owner(), new_owner);
     }
 
-    fn test_validator2() -> PublicKey {
-        // Second valid 32-byte Ed25519 public key
-        let mut bytes = [0u8; 32];
-        bytes[0] = 0x02;
-        bytes[31] = 0x02;
-        PublicKey::ed25519_from_bytes(bytes).unwrap()
+    #[test]
+    fn test_multisig_gates_owner_config_and_timelock() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let approver = env.get_account(1);
+        let manager = env.get_account(2);
+        let alice = env.get_account(3);
+
+        env.set_caller(owner);
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let queued = contract.queue_set_protocol_fee(1_000, eta);
+        contract.add_approver(approver);
+        contract.set_approval_threshold(1);
+
+        // Every owner-only config, treasury and timelock action needs a proposal now
+        let required: Result<(), odra::OdraError> = Err(Error::ApprovalRequired.into());
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        assert_eq!(contract.try_queue_set_emergency_timeout(1_000, eta).map(|_| ()), required);
+        assert_eq!(contract.try_queue_set_protocol_fee(500, eta).map(|_| ()), required);
+        assert_eq!(contract.try_queue_set_rebasing_view(manager, eta).map(|_| ()), required);
+        assert_eq!(contract.try_set_referral_fee_share_bps(5_000), required);
+        assert_eq!(contract.try_set_allowlist_enabled(true), required);
+        assert_eq!(contract.try_set_allowlist_manager(manager), required);
+        assert_eq!(contract.try_add_to_allowlist(vec![alice]), required);
+        assert_eq!(contract.try_remove_from_allowlist(vec![alice]), required);
+        assert_eq!(contract.try_set_tvl_cap(U512::from(MIN_DELEGATION)), required);
+        assert_eq!(contract.try_set_max_stake_per_account(U512::from(MIN_DELEGATION)), required);
+        assert_eq!(contract.try_set_min_stake_amount(U512::one()), required);
+        assert_eq!(contract.try_admin_credit_purse_liquidity(U512::one()), required);
+        assert_eq!(contract.try_release_legacy_reserve().map(|_| ()), required);
+        assert_eq!(
+            contract.with_tokens(U512::from(MIN_DELEGATION)).try_import_legacy_positions([1u8; 32]),
+            required
+        );
+        env.advance_block_time(TIMELOCK_DELAY);
+        assert_eq!(contract.try_execute_operation(queued), required);
+        assert_eq!(contract.try_cancel_operation(queued), required);
+
+        // The same actions go through approvers
+        env.set_caller(approver);
+        let proposal_id = contract.create_proposal(ProposalKind::SetAllowlistManager, None, Some(manager), U512::zero());
+        contract.execute_proposal(proposal_id);
+        let proposal_id = contract.create_proposal(ProposalKind::SetTvlCap, None, None, U512::from(MIN_DELEGATION));
+        contract.execute_proposal(proposal_id);
+        let proposal_id = contract.create_proposal(ProposalKind::SetReferralFeeShare, None, None, U512::from(5_000u64));
+        contract.execute_proposal(proposal_id);
+        let proposal_id = contract.create_proposal(ProposalKind::ExecuteOperation, None, None, U512::from(queued));
+        contract.execute_proposal(proposal_id);
+        assert_eq!(contract.get_allowlist_manager(), Some(manager));
+        assert_eq!(contract.get_tvl_cap(), U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_referral_fee_share_bps(), 5_000);
+        assert_eq!(contract.get_protocol_fee_bps(), 1_000);
+
+        // The allowlist manager role itself is not an owner action
+        env.set_caller(manager);
+        contract.add_to_allowlist(vec![alice]);
+        assert!(contract.is_allowlisted(alice));
     }
 
-    fn unapproved_validator() -> PublicKey {
-        // Third valid 32-byte Ed25519 public key (not approved)
-        let mut bytes = [0u8; 32];
-        bytes[0] = 0x03;
-        bytes[31] = 0x03;
-        PublicKey::ed25519_from_bytes(bytes).unwrap()
+    #[test]
+    #[should_panic(expected = "NotApprover")]
+    fn test_non_approver_cannot_propose() {
+        let (env, mut contract) = setup();
+        env.set_caller(env.get_account(5));
+        contract.create_proposal(ProposalKind::AdminUndelegate, Some(test_validator()), None, U512::one());
     }
 
-    fn setup() -> (odra::host::HostEnv, StakeVueHostRef) {
-        let env = odra_test::env();
+    #[test]
+    fn test_multisig_gates_validator_admin_and_claims() {
+        let (env, mut contract) = setup();
         let owner = env.get_account(0);
-        let mut contract = StakeVue::deploy(&env, StakeVueInitArgs { owner });
+        let approver1 = env.get_account(1);
+        let approver2 = env.get_account(2);
 
-        // Add test validator
-        env.set_caller(owner);
-        contract.add_validator(test_validator());
+        env.set_caller(env.get_account(3));
+        contract.with_tokens(U512::from(MIN_DELEGATION * 2)).stake(test_validator());
+        let request_id = contract.request_unstake(U512::from(MIN_DELEGATION));
 
-        (env, contract)
+        env.set_caller(owner);
+        contract.add_approver(approver1);
+        contract.add_approver(approver2);
+        contract.set_approval_threshold(2);
+
+        assert_eq!(contract.try_add_validator(test_validator2()), Err(Error::ApprovalRequired.into()));
+        assert_eq!(
+            contract.try_set_validator_max_allocation(test_validator(), U512::one()),
+            Err(Error::ApprovalRequired.into())
+        );
+        assert_eq!(contract.try_set_max_validator_share_bps(5_000), Err(Error::ApprovalRequired.into()));
+        env.advance_block_time(UNBONDING_BLOCKS);
+        assert_eq!(contract.try_admin_process_claim(request_id), Err(Error::ApprovalRequired.into()));
+
+        env.set_caller(approver1);
+        let add = contract.create_proposal(ProposalKind::AddValidator, Some(test_validator2()), None, U512::zero());
+        let claim = contract.create_proposal(ProposalKind::AdminProcessClaim, None, None, U512::from(request_id));
+        env.set_caller(approver2);
+        contract.approve_proposal(add);
+        contract.approve_proposal(claim);
+        contract.execute_proposal(add);
+        contract.execute_proposal(claim);
+        assert!(contract.is_validator_active(test_validator2()));
+        assert!(contract.is_withdrawal_claimed(request_id));
     }
 
     #[test]
-    fn test_initial_state() {
-        let (_env, contract) = setup();
-        assert_eq!(contract.get_exchange_rate(), U512::from(RATE_PRECISION));
-        assert_eq!(contract.get_validator_count(), 1);
-        assert!(contract.is_validator_active(test_validator()));
+    fn test_multisig_recounts_approvals_at_execution() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let approver1 = env.get_account(1);
+        let approver2 = env.get_account(2);
+        let approver3 = env.get_account(3);
+
+        // Threshold 0: proposals can't bypass the owner
+        env.set_caller(owner);
+        contract.add_approver(approver1);
+        env.set_caller(approver1);
+        let proposal_id = contract.create_proposal(ProposalKind::AddApprover, None, Some(approver2), U512::zero());
+        assert!(!contract.is_proposal_approved(proposal_id));
+        assert_eq!(contract.try_execute_proposal(proposal_id), Err(Error::MultisigDisabled.into()));
+
+        env.set_caller(owner);
+        contract.add_approver(approver2);
+        contract.add_approver(approver3);
+        contract.set_approval_threshold(2);
+
+        // Approved by 1 and 2, then 2 is removed: one current approval left
+        env.set_caller(approver1);
+        let proposal_id = contract.create_proposal(ProposalKind::SetMaxValidatorShare, None, None, U512::from(5_000u64));
+        env.set_caller(approver2);
+        contract.approve_proposal(proposal_id);
+        assert!(contract.is_proposal_approved(proposal_id));
+
+        env.set_caller(approver1);
+        let removal = contract.create_proposal(ProposalKind::RemoveApprover, None, Some(approver2), U512::zero());
+        env.set_caller(approver3);
+        contract.approve_proposal(removal);
+        contract.execute_proposal(removal);
+
+        assert!(!contract.is_proposal_approved(proposal_id));
+        assert_eq!(contract.try_execute_proposal(proposal_id), Err(Error::ThresholdNotReached.into()));
+        contract.approve_proposal(proposal_id);
+        contract.execute_proposal(proposal_id);
+        assert_eq!(contract.get_max_validator_share_bps(), 5_000);
+
+        let proposal_id = contract.create_proposal(ProposalKind::TransferOwnership, None, None, U512::zero());
+        env.set_caller(approver1);
+        contract.approve_proposal(proposal_id);
+        assert_eq!(contract.try_execute_proposal(proposal_id), Err(Error::NoAccountSet.into()));
     }
 
     #[test]
-    fn test_add_multiple_validators() {
+    fn test_deposit_limits() {
         let (env, mut contract) = setup();
         let owner = env.get_account(0);
+        let alice = env.get_account(1);
+        let bob = env.get_account(2);
+
         env.set_caller(owner);
+        contract.set_tvl_cap(U512::from(MIN_DELEGATION * 3));
+        contract.set_max_stake_per_account(U512::from(MIN_DELEGATION * 2));
+        contract.set_min_stake_amount(U512::from(10_000_000_000u64));
+        assert_eq!(contract.get_remaining_account_capacity(alice), U512::from(MIN_DELEGATION * 2));
 
-        contract.add_validator(test_validator2());
+        env.set_caller(alice);
+        let result = contract.with_tokens(U512::from(1_000_000_000u64)).try_stake(test_validator());
+        assert_eq!(result, Err(Error::BelowMinimumStake.into()));
 
-        assert_eq!(contract.get_validator_count(), 2);
-        assert!(contract.is_validator_active(test_validator()));
-        assert!(contract.is_validator_active(test_validator2()));
+        contract.with_tokens(U512::from(MIN_DELEGATION * 2)).stake(test_validator());
+        assert_eq!(contract.get_remaining_capacity(), U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_remaining_account_capacity(alice), U512::zero());
+
+        let result = contract.with_tokens(U512::from(MIN_DELEGATION)).try_stake(test_validator());
+        assert_eq!(result, Err(Error::AccountStakeLimitExceeded.into()));
+
+        env.set_caller(bob);
+        let result = contract.with_tokens(U512::from(MIN_DELEGATION * 2)).try_stake(test_validator());
+        assert_eq!(result, Err(Error::TvlCapExceeded.into()));
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+        assert_eq!(contract.get_remaining_capacity(), U512::zero());
     }
 
     #[test]
-    fn test_stake_to_validator() {
+    fn test_allowlist_mode() {
         let (env, mut contract) = setup();
-        let staker = env.get_account(1);
-        env.set_caller(staker);
+        let owner = env.get_account(0);
+        let manager = env.get_account(1);
+        let partner = env.get_account(2);
+        let outsider = env.get_account(3);
 
-        let stake_amount = U512::from(MIN_DELEGATION);
-        contract.with_tokens(stake_amount).stake(test_validator());
+        env.set_caller(owner);
+        contract.set_allowlist_manager(manager);
+        contract.set_allowlist_enabled(true);
 
-        // V20: stake adds to pool and available_liquidity (not validator_delegated)
-        assert_eq!(contract.get_stcspr_balance(staker), U256::from(MIN_DELEGATION));
-        assert_eq!(contract.get_total_pool(), stake_amount);
-        assert_eq!(contract.get_available_liquidity(), stake_amount);
-        // Validator delegated is 0 until admin calls admin_delegate
-        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::zero());
+        env.set_caller(partner);
+        let result = contract.with_tokens(U512::from(MIN_DELEGATION)).try_stake(test_validator());
+        assert_eq!(result, Err(Error::NotAllowlisted.into()));
+
+        env.set_caller(outsider);
+        let result = contract.try_add_to_allowlist(vec![outsider]);
+        assert_eq!(result, Err(Error::NotAllowlistManager.into()));
+
+        env.set_caller(manager);
+        contract.add_to_allowlist(vec![partner, manager]);
+        assert!(contract.is_allowlisted(partner));
+
+        env.set_caller(partner);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+
+        // Transfers only between allowlisted accounts
+        let result = contract.try_transfer(outsider, U256::from(1u64));
+        assert_eq!(result, Err(Error::NotAllowlisted.into()));
+        contract.transfer(manager, U256::from(1u64));
+        assert_eq!(contract.get_stcspr_balance(manager), U256::from(1u64));
+
+        // Outsiders can't be made spenders or pull tokens
+        let result = contract.try_approve(outsider, U256::from(1u64));
+        assert_eq!(result, Err(Error::NotAllowlisted.into()));
+        contract.approve(manager, U256::from(1u64));
+        env.set_caller(outsider);
+        let result = contract.try_transfer_from(partner, manager, U256::from(1u64));
+        assert_eq!(result, Err(Error::NotAllowlisted.into()));
+        let result = contract.try_claim_referral_rewards();
+        assert_eq!(result, Err(Error::NotAllowlisted.into()));
+
+        // Permissionless again once disabled
+        env.set_caller(owner);
+        contract.set_allowlist_enabled(false);
+        env.set_caller(partner);
+        contract.transfer(outsider, U256::from(1u64));
+        assert_eq!(contract.get_stcspr_balance(outsider), U256::from(1u64));
     }
 
     #[test]
-    fn test_stake_to_multiple_validators() {
+    fn test_referral_rewards() {
         let (env, mut contract) = setup();
         let owner = env.get_account(0);
-        let staker = env.get_account(1);
+        let referrer = env.get_account(1);
+        let staker = env.get_account(2);
+        let other_referrer = env.get_account(3);
 
-        // Add second validator
         env.set_caller(owner);
-        contract.add_validator(test_validator2());
+        contract.set_referral_fee_share_bps(5_000);
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let operation_id = contract.queue_set_protocol_fee(1_000, eta);
+        env.advance_block_time(TIMELOCK_DELAY);
+        contract.execute_operation(operation_id);
+        assert_eq!(contract.get_protocol_fee_bps(), 1_000);
+        assert_eq!(contract.get_referral_fee_share_bps(), 5_000);
 
-        // Stake (validator param is just for routing preference in V20)
         env.set_caller(staker);
-        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
-        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator2());
-
-        // V20: all CSPR goes to available_liquidity
-        assert_eq!(contract.get_stcspr_balance(staker), U256::from(MIN_DELEGATION * 2));
-        assert_eq!(contract.get_available_liquidity(), U512::from(MIN_DELEGATION * 2));
+        let result = contract.with_tokens(U512::from(MIN_DELEGATION)).try_stake_with_referral(test_validator(), staker);
+        assert_eq!(result, Err(Error::SelfReferral.into()));
+        contract.with_tokens(U512::from(MIN_DELEGATION * 2)).stake_with_referral(test_validator(), referrer);
+        assert_eq!(contract.get_referrer(staker), Some(referrer));
+        assert_eq!(contract.get_referred_tvl(referrer), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(contract.get_referral_count(referrer), 1);
+
+        // A second deposit can't silently credit someone else
+        let result = contract
+            .with_tokens(U512::from(MIN_DELEGATION))
+            .try_stake_with_referral(test_validator(), other_referrer);
+        assert_eq!(result, Err(Error::ReferrerMismatch.into()));
+
+        // 100 CSPR rewards -> 10 CSPR fee (10 * 1000 / 1090 stCSPR at the post-harvest
+        // rate); the whole pool is referred, so half goes to the referrer, half to the owner
+        env.set_caller(owner);
+        contract.with_tokens(U512::from(100_000_000_000u64)).harvest_rewards();
+        let pending = contract.get_pending_referral_rewards(referrer);
+        assert_eq!(pending, U256::from(4_587_155_963u64));
+        assert_eq!(contract.get_stcspr_balance(owner), U256::from(4_587_155_963u64));
+
+        env.set_caller(referrer);
+        let claimed = contract.claim_referral_rewards();
+        assert_eq!(claimed, pending);
+        assert_eq!(contract.get_stcspr_balance(referrer), pending);
+        // 5 CSPR less one mote of rounding
+        assert_eq!(contract.get_cspr_value(referrer), U512::from(4_999_999_999u64));
+
+        // Unstaking 500 stCSPR (545 CSPR) releases that much referred TVL
+        env.set_caller(staker);
+        contract.request_unstake(U512::from(MIN_DELEGATION));
+        assert_eq!(contract.get_referred_tvl(referrer), U512::from(455_000_000_000u64));
     }
 
     #[test]
-    fn test_admin_delegate() {
+    fn test_referral_fee_limited_to_referred_principal() {
         let (env, mut contract) = setup();
         let owner = env.get_account(0);
-        let staker = env.get_account(1);
+        let referrer = env.get_account(1);
+        let staker = env.get_account(2);
+        let whale = env.get_account(3);
 
-        // User stakes
+        env.set_caller(owner);
+        contract.set_referral_fee_share_bps(5_000);
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let operation_id = contract.queue_set_protocol_fee(1_000, eta);
+        env.advance_block_time(TIMELOCK_DELAY);
+        contract.execute_operation(operation_id);
+
+        // 500 of 10_000 CSPR referred
+        env.set_caller(whale);
+        contract.with_tokens(U512::from(MIN_DELEGATION * 19)).stake(test_validator());
         env.set_caller(staker);
-        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake_with_referral(test_validator(), referrer);
 
-        // Admin delegates to validator
+        // 1_000 CSPR rewards -> 100 CSPR fee = 91_743_119_266 stCSPR. Referrers share
+        // half of the fee on the referred 5% only: fee / 40, the rest to the owner
         env.set_caller(owner);
-        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+        contract.with_tokens(U512::from(MIN_DELEGATION * 2)).harvest_rewards();
+        assert_eq!(contract.get_pending_referral_rewards(referrer), U256::from(2_293_577_981u64));
+        assert_eq!(contract.get_stcspr_balance(owner), U256::from(89_449_541_285u64));
 
-        // Now validator has delegation, liquidity is 0
-        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::from(MIN_DELEGATION));
-        assert_eq!(contract.get_available_liquidity(), U512::zero());
+        // Passing stCSPR on releases the principal behind it, by transfer or transfer_from
+        env.set_caller(staker);
+        contract.transfer(whale, U256::from(100_000_000_000u64));
+        assert_eq!(contract.get_referred_tvl(referrer), U512::from(400_000_000_000u64));
+        contract.approve(whale, U256::from(100_000_000_000u64));
+        env.set_caller(whale);
+        contract.transfer_from(staker, whale, U256::from(100_000_000_000u64));
+        assert_eq!(contract.get_referred_tvl(referrer), U512::from(300_000_000_000u64));
+
+        // Rewards accrued before the transfers stay with the referrer
+        assert_eq!(contract.get_pending_referral_rewards(referrer), U256::from(2_293_577_981u64));
     }
 
     #[test]
-    #[should_panic(expected = "ValidatorNotApproved")]
-    fn test_stake_to_unapproved_validator_fails() {
-        let (env, contract) = setup();
-        env.set_caller(env.get_account(1));
-        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(unapproved_validator());
+    fn test_stake_for_and_request_unstake_for() {
+        let (env, mut contract) = setup();
+        let owner = env.get_account(0);
+        let custodian = env.get_account(1);
+        let client = env.get_account(2);
+        let receiver = env.get_account(3);
+
+        env.set_caller(custodian);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake_for(client, test_validator());
+        assert_eq!(contract.get_stcspr_balance(custodian), U256::zero());
+        assert_eq!(contract.get_stcspr_balance(client), U256::from(MIN_DELEGATION));
+        assert!(env.emitted_event(
+            &contract,
+            StakedFor { payer: custodian, recipient: client, cspr_amount: U512::from(MIN_DELEGATION) }
+        ));
+
+        env.set_caller(client);
+        let request_id = contract.request_unstake_for(U512::from(MIN_DELEGATION), receiver);
+        assert_eq!(contract.get_withdrawal_staker(request_id), Some(client));
+        assert_eq!(contract.get_withdrawal_receiver(request_id), Some(receiver));
+
+        env.advance_block_time(UNBONDING_BLOCKS);
+        let receiver_balance = env.balance_of(&receiver);
+        env.set_caller(owner);
+        contract.admin_process_claim(request_id);
+        assert_eq!(env.balance_of(&receiver), receiver_balance + U512::from(MIN_DELEGATION));
     }
 
     #[test]
-    fn test_request_unstake() {
+    fn test_permit() {
         let (env, mut contract) = setup();
-        let staker = env.get_account(1);
-        env.set_caller(staker);
+        let holder = env.get_account(1);
+        let router = env.get_account(2);
+        let recipient = env.get_account(3);
 
-        // Stake first
+        env.set_caller(holder);
         contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
 
-        // Request unstake (V20: no validator param needed)
-        let request_id = contract.request_unstake(U512::from(MIN_DELEGATION));
+        let amount = U256::from(MIN_DELEGATION / 2);
+        let deadline = env.block_time() + 60_000;
+        let digest = contract.get_permit_digest(holder, router, amount, 0, deadline);
+        let signature = env.sign_message(&Bytes::from(digest.to_vec()), &holder);
+
+        // Router submits the permit and pulls the tokens, holder sends nothing
+        env.set_caller(router);
+        contract.permit(env.public_key(&holder), router, amount, deadline, signature.clone());
+        assert_eq!(contract.allowance(holder, router), amount);
+        assert_eq!(contract.get_permit_nonce(holder), 1);
+
+        contract.transfer_from(holder, recipient, amount);
+        assert_eq!(contract.get_stcspr_balance(recipient), amount);
+        assert_eq!(contract.allowance(holder, router), U256::zero());
+
+        // Replay fails: nonce moved on
+        let result = contract.try_permit(env.public_key(&holder), router, amount, deadline, signature);
+        assert_eq!(result, Err(Error::InvalidSignature.into()));
+
+        // A permit is an ordinary allowance: approve(spender, 0) revokes it
+        let digest = contract.get_permit_digest(holder, router, amount, 1, deadline);
+        let signature = env.sign_message(&Bytes::from(digest.to_vec()), &holder);
+        contract.permit(env.public_key(&holder), router, amount, deadline, signature);
+        env.set_caller(holder);
+        contract.approve(router, U256::zero());
+        assert_eq!(contract.allowance(holder, router), U256::zero());
+        env.set_caller(router);
+        let result = contract.try_transfer_from(holder, recipient, U256::one());
+        assert_eq!(result, Err(Error::InsufficientAllowance.into()));
+    }
 
-        assert_eq!(request_id, 1);
-        assert_eq!(contract.get_stcspr_balance(staker), U256::zero());
-        assert_eq!(contract.get_pending_withdrawals(), U512::from(MIN_DELEGATION));
-        assert_eq!(contract.get_pending_undelegations(), U512::from(MIN_DELEGATION));
-        assert_eq!(contract.get_user_request_count(staker), 1);
+    #[test]
+    #[should_panic(expected = "PermitExpired")]
+    fn test_permit_expired() {
+        let (env, mut contract) = setup();
+        let holder = env.get_account(1);
+        let router = env.get_account(2);
+
+        let digest = contract.get_permit_digest(holder, router, U256::one(), 0, 0);
+        let signature = env.sign_message(&Bytes::from(digest.to_vec()), &holder);
+
+        env.advance_block_time(1);
+        env.set_caller(router);
+        contract.permit(env.public_key(&holder), router, U256::one(), 0, signature);
     }
 
     #[test]
-    fn test_admin_undelegate() {
+    fn test_rebasing_view_consistent_through_harvest() {
         let (env, mut contract) = setup();
         let owner = env.get_account(0);
-        let staker = env.get_account(1);
-
-        // User stakes
-        env.set_caller(staker);
-        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
+        let alice = env.get_account(1);
+        let bob = env.get_account(2);
+        let mut view = RebasingStCspr::deploy(&env, RebasingStCsprInitArgs { stcspr: contract.address() });
 
-        // Admin delegates
+        // Registered once, through the timelock, and only as a contract
         env.set_caller(owner);
-        contract.admin_delegate(test_validator(), U512::from(MIN_DELEGATION));
+        let eta = env.block_time() + TIMELOCK_DELAY;
+        let result = contract.try_queue_set_rebasing_view(owner, eta);
+        assert_eq!(result, Err(Error::NotRebasingView.into()));
+        let operation_id = contract.queue_set_rebasing_view(view.address(), eta);
+        env.advance_block_time(TIMELOCK_DELAY);
+        contract.execute_operation(operation_id);
+        assert_eq!(contract.get_rebasing_view(), Some(view.address()));
+        let result = contract.try_queue_set_rebasing_view(view.address(), env.block_time() + TIMELOCK_DELAY);
+        assert_eq!(result, Err(Error::RebasingViewAlreadySet.into()));
+
+        env.set_caller(alice);
+        contract.with_tokens(U512::from(MIN_DELEGATION * 2)).stake(test_validator());
+        env.set_caller(bob);
+        contract.with_tokens(U512::from(MIN_DELEGATION)).stake(test_validator());
 
-        // User requests unstake
-        env.set_caller(staker);
-        contract.request_unstake(U512::from(MIN_DELEGATION));
+        // 1:1 before rewards
+        assert_eq!(view.balance_of(alice), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(view.total_supply(), contract.get_total_pool());
 
-        // Admin undelegates
+        // 10% rewards: rebasing balances grow, shares don't
         env.set_caller(owner);
-        contract.admin_undelegate(test_validator(), U512::from(MIN_DELEGATION));
+        contract.with_tokens(U512::from(150_000_000_000u64)).harvest_rewards();
+        assert_eq!(contract.get_stcspr_balance(alice), U256::from(MIN_DELEGATION * 2));
+        assert_eq!(view.balance_of(alice), U512::from(1_100_000_000_000u64));
+        assert_eq!(view.balance_of(bob), U512::from(550_000_000_000u64));
+        assert_eq!(view.total_supply(), contract.get_total_pool());
+
+        // Rebasing transfer of 110 CSPR moves 100 stCSPR shares, once the
+        // holder has approved the view for them
+        env.set_caller(alice);
+        assert!(view.try_transfer(bob, U512::from(110_000_000_000u64)).is_err());
+        contract.approve(view.address(), U256::from(100_000_000_000u64));
+        let shares = view.transfer(bob, U512::from(110_000_000_000u64));
+        assert_eq!(shares, U256::from(100_000_000_000u64));
+        assert_eq!(contract.get_stcspr_balance(bob), U256::from(MIN_DELEGATION + 100_000_000_000u64));
+        assert_eq!(view.balance_of(alice), U512::from(990_000_000_000u64));
+        assert_eq!(view.balance_of(bob), U512::from(660_000_000_000u64));
+
+        // transfer_from spends the share allowance given on StakeVue
+        contract.approve(owner, U256::from(50_000_000_000u64));
+        env.set_caller(owner);
+        assert!(view.try_transfer_from(alice, owner, U512::from(56_000_000_000u64)).is_err());
+        assert_eq!(view.transfer_from(alice, bob, U512::from(55_000_000_000u64)), U256::from(50_000_000_000u64));
+        assert_eq!(contract.allowance(alice, owner), U256::zero());
+
+        // Both views agree for every holder and in total
+        for account in [alice, bob] {
+            assert_eq!(view.balance_of(account), contract.get_cspr_value(account));
+            assert_eq!(view.balance_of(account), view.get_cspr_by_shares(contract.get_stcspr_balance(account)));
+        }
+        assert_eq!(view.balance_of(alice) + view.balance_of(bob), view.total_supply());
 
-        assert_eq!(contract.get_delegated_to_validator(test_validator()), U512::zero());
-        assert_eq!(contract.get_pending_undelegations(), U512::zero());
+        // Only the registered view moves shares, and never without an allowance
+        env.set_caller(bob);
+        assert_eq!(
+            contract.try_rebasing_view_transfer(alice, alice, bob, U256::one()),
+            Err(Error::NotRebasingView.into())
+        );
+        assert!(view.try_transfer_from(alice, bob, U512::from(1_000_000_000u64)).is_err());
+        assert_eq!(contract.allowance(alice, view.address()), U256::zero());
     }
 
     #[test]
-    fn test_harvest_rewards() {
+    fn test_rate_history() {
         let (env, mut contract) = setup();
         let owner = env.get_account(0);
-        let staker = env.get_account(1);
 
-        // Stake
-        env.set_caller(staker);
-        contract.with_tokens(U512::from(1000_000_000_000u64)).stake(test_validator());
+        env.set_caller(env.get_account(1));
+        contract.with_tokens(U512::from(1_000_000_000_000u64)).stake(test_validator());
 
-        // Harvest rewards
+        // +1% after half a year -> ~2% APR
         env.set_caller(owner);
-        contract.with_tokens(U512::from(100_000_000_000u64)).harvest_rewards();
-
-        // Exchange rate should increase
-        assert_eq!(contract.get_total_pool(), U512::from(1100_000_000_000u64));
-        // Rate = 1100/1000 * 1e9 = 1.1e9
-        assert_eq!(contract.get_exchange_rate(), U512::from(1_100_000_000u64));
+        contract.sync_rate_snapshot();
+        env.advance_block_time(YEAR_MS / 2);
+        contract.with_tokens(U512::from(10_000_000_000u64)).harvest_rewards();
+
+        assert_eq!(contract.get_rate_snapshot_count(), 2);
+        let first = contract.get_rate_at(0).unwrap();
+        let second = contract.get_rate_at(1).unwrap();
+        assert_eq!(first.rate, U512::from(RATE_PRECISION));
+        assert_eq!(second.rate, U512::from(1_010_000_000u64));
+        assert_eq!(second.total_pool, U512::from(1_010_000_000_000u64));
+        assert_eq!(contract.get_estimated_apr_bps(1), 200);
+        assert_eq!(contract.get_rate_history(0, 10), vec![first, second]);
+        assert!(contract.get_rate_at(2).is_none());
     }
 
     #[test]
-    #[should_panic(expected = "BelowMinimumDelegation")]
-    fn test_admin_delegate_below_minimum_fails() {
+    fn test_rate_history_ring_buffer_wraps() {
         let (env, mut contract) = setup();
-        let owner = env.get_account(0);
-        let staker = env.get_account(1);
+        env.set_caller(env.get_account(0));
 
-        // User stakes (any amount is ok in V20)
-        env.set_caller(staker);
-        contract.with_tokens(U512::from(100_000_000_000u64)).stake(test_validator());
+        for _ in 0..RATE_HISTORY_SIZE + 3 {
+            contract.sync_rate_snapshot();
+        }
 
-        // Admin tries to delegate below minimum - should fail
-        env.set_caller(owner);
-        contract.admin_delegate(test_validator(), U512::from(100_000_000_000u64));
+        assert_eq!(contract.get_rate_snapshot_count(), RATE_HISTORY_SIZE + 3);
+        assert!(contract.get_rate_at(2).is_none());
+        assert!(contract.get_rate_at(3).is_some());
+        assert_eq!(contract.get_rate_history(0, 1000).len() as u64, MAX_RATE_HISTORY_PAGE);
+        assert_eq!(contract.get_rate_history(RATE_HISTORY_SIZE - 1, 10).len(), 1);
     }
 
     #[test]
-    fn test_stake_any_amount_succeeds() {
+    fn test_migrate_is_noop_when_current() {
         let (env, mut contract) = setup();
-        let staker = env.get_account(1);
-        env.set_caller(staker);
+        env.set_caller(env.get_account(0));
+        contract.migrate();
+        assert_eq!(contract.get_contract_version(), CONTRACT_VERSION);
+        assert!(!env.emitted(&contract, "ContractMigrated"));
+    }
 
-        // V20: stake accepts any amount (minimum is checked on admin_delegate)
-        contract.with_tokens(U512::from(100_000_000_000u64)).stake(test_validator());
-        contract.with_tokens(U512::from(100_000_000_000u64)).stake(test_validator());
+    /// StakeVue as installed before contract_version existed: the first 14
+    /// fields of today's struct, with just enough entry points to leave
+    /// stakers, a delegation and a withdrawal request behind
+    #[odra::module]
+    pub struct PreVersioningStakeVue {
+        ownable: SubModule<Ownable>,
+        token: SubModule<Cep18>,
+        total_cspr_pool: Var<U512>,
+        available_liquidity: Var<U512>,
+        pending_withdrawals: Var<U512>,
+        pending_undelegations: Var<U512>,
+        validators: Mapping<u8, PublicKey>,
+        validator_count: Var<u8>,
+        validator_active: Mapping<PublicKey, bool>,
+        validator_delegated: Mapping<PublicKey, U512>,
+        withdrawal_requests: Mapping<u64, WithdrawalRequest>,
+        next_request_id: Var<u64>,
+        user_requests: Mapping<(Address, u64), u64>,
+        user_request_count: Mapping<Address, u64>,
+    }
 
-        assert_eq!(contract.get_stcspr_balance(staker), U256::from(200_000_000_000u64));
-        assert_eq!(contract.get_available_liquidity(), U512::from(200_000_000_000u64));
+    #[odra::module]
+    impl PreVersioningStakeVue {
+        pub fn init(&mut self, owner: Address) {
+            self.ownable.init(owner);
+            self.next_request_id.set(1);
+            self.token.init(String::from("stCSPR"), String::from("Staked CSPR"), 9, U256::zero());
+        }
+
+        pub fn add_validator(&mut self, validator: PublicKey) {
+            let count = self.validator_count.get_or_default();
+            self.validators.set(&count, validator.clone());
+            self.validator_active.set(&validator, true);
+            self.validator_count.set(count + 1);
+        }
+
+        /// Stake at 1:1, as before any rewards
+        #[odra(payable)]
+        pub fn stake(&mut self) {
+            let amount = self.env().attached_value();
+            self.token.raw_mint(&self.env().caller(), &u512_to_u256(amount));
+            self.total_cspr_pool.set(self.total_cspr_pool.get_or_default() + amount);
+            self.available_liquidity.set(self.available_liquidity.get_or_default() + amount);
+        }
+
+        pub fn delegate(&mut self, validator: PublicKey, amount: U512) {
+            self.available_liquidity.set(self.available_liquidity.get_or_default() - amount);
+            let delegated = self.validator_delegated.get(&validator).unwrap_or_default();
+            self.validator_delegated.set(&validator, delegated + amount);
+        }
+
+        pub fn request_unstake(&mut self, amount: U512) -> u64 {
+            let staker = self.env().caller();
+            self.token.raw_burn(&staker, &u512_to_u256(amount));
+            self.total_cspr_pool.set(self.total_cspr_pool.get_or_default() - amount);
+            self.pending_withdrawals.set(self.pending_withdrawals.get_or_default() + amount);
+            self.pending_undelegations.set(self.pending_undelegations.get_or_default() + amount);
+
+            let request_id = self.next_request_id.get_or_default();
+            self.next_request_id.set(request_id + 1);
+            self.withdrawal_requests.set(&request_id, WithdrawalRequest {
+                staker,
+                cspr_amount: amount,
+                request_block: self.env().get_block_time(),
+                claimed: false,
+            });
+            let count = self.user_request_count.get(&staker).unwrap_or(0);
+            self.user_requests.set(&(staker, count), request_id);
+            self.user_request_count.set(&staker, count + 1);
+            request_id
+        }
     }
 
     #[test]
-    fn test_remove_validator() {
-        let (env, mut contract) = setup();
+    fn test_upgrade_from_pre_versioning_layout_keeps_positions() {
+        let env = odra_test::env();
         let owner = env.get_account(0);
+        let alice = env.get_account(1);
+        let bob = env.get_account(2);
+
+        let mut old = PreVersioningStakeVue::deploy_with_cfg(
+            &env,
+            PreVersioningStakeVueInitArgs { owner },
+            InstallConfig::upgradable::<StakeVue>(),
+        );
+        old.add_validator(test_validator());
+        env.set_caller(alice);
+        old.with_tokens(U512::from(MIN_DELEGATION * 2)).stake();
+        env.set_caller(bob);
+        old.with_tokens(U512::from(MIN_DELEGATION)).stake();
+        let request_id = old.request_unstake(U512::from(MIN_DELEGATION / 2));
         env.set_caller(owner);
+        old.delegate(test_validator(), U512::from(MIN_DELEGATION * 2));
+
+        env.advance_block_time(1_000);
+        let mut upgraded = StakeVue::try_upgrade(&env, old.address(), NoArgs).unwrap();
+
+        // Every pre-existing slot reads back through the new layout
+        assert_eq!(upgraded.get_owner(), owner);
+        assert_eq!(upgraded.get_stcspr_balance(alice), U256::from(MIN_DELEGATION * 2));
+        assert_eq!(upgraded.get_stcspr_balance(bob), U256::from(MIN_DELEGATION / 2));
+        assert_eq!(upgraded.get_total_pool(), U512::from(MIN_DELEGATION * 5 / 2));
+        assert_eq!(upgraded.get_available_liquidity(), U512::from(MIN_DELEGATION));
+        assert_eq!(upgraded.get_pending_withdrawals(), U512::from(MIN_DELEGATION / 2));
+        assert_eq!(upgraded.get_delegated_to_validator(test_validator()), U512::from(MIN_DELEGATION * 2));
+        assert_eq!(upgraded.get_withdrawal_staker(request_id), Some(bob));
+        assert_eq!(upgraded.get_withdrawal_amount(request_id), U512::from(MIN_DELEGATION / 2));
+        assert_eq!(upgraded.get_user_request_id(bob, 0), Some(request_id));
+
+        // Fields appended since were empty and got backfilled
+        assert_eq!(upgraded.get_contract_version(), CONTRACT_VERSION);
+        assert!(env.emitted_event(
+            &upgraded,
+            ContractMigrated { from_version: LEGACY_CONTRACT_VERSION, to_version: CONTRACT_VERSION }
+        ));
+        let info = upgraded.get_validator_info(test_validator()).unwrap();
+        assert_eq!(info.added_at, env.block_time());
+        assert_eq!(info.max_allocation, U512::zero());
+        assert_eq!(upgraded.get_last_admin_activity(), env.block_time());
+
+        // And the upgraded pool keeps working on top of the old state
+        env.set_caller(alice);
+        let next_id = upgraded.request_unstake(U512::from(MIN_DELEGATION));
+        assert_eq!(next_id, request_id + 1);
+        assert_eq!(upgraded.get_pending_withdrawals(), U512::from(MIN_DELEGATION * 3 / 2));
+    }
 
-        contract.remove_validator(test_validator());
-        assert!(!contract.is_validator_active(test_validator()));
+    #[test]
+    fn test_upgrade_requires_owner() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let contract = StakeVue::deploy_with_cfg(
+            &env,
+            StakeVueInitArgs { owner },
+            InstallConfig::upgradable::<StakeVue>(),
+        );
+
+        env.set_caller(env.get_account(1));
+        assert!(StakeVue::try_upgrade(&env, contract.address(), NoArgs).is_err());
+        env.set_caller(owner);
+        assert!(StakeVue::try_upgrade(&env, contract.address(), NoArgs).is_ok());
     }
 }
@@ -0,0 +1,237 @@
+//! Deploy lookup over the node's JSON-RPC for the livenet binaries.
+//!
+//! Odra's livenet env sends a deploy and returns only the call result, not
+//! the deploy hash. The bot therefore notes the chain height before each
+//! submission and afterwards scans the blocks from there for a deploy from
+//! its account calling the action's entry point, directly or through
+//! Odra's proxy caller for payable calls. The hash found goes into the
+//! journal, and the deploy's execution result settles entries whose
+//! outcome was never seen. Casper 1.x and 2.x response shapes are both
+//! understood.
+
+use std::format;
+use std::string::{String, ToString};
+use std::time::Duration;
+use std::vec::Vec;
+
+use odra::casper_types::{AsymmetricType, PublicKey};
+use odra::prelude::Address;
+use serde_json::{json, Value};
+
+/// Most blocks scanned by one lookup, so an old height can't stall a cycle.
+const MAX_BLOCKS_SCANNED: u64 = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeployOutcome {
+    Succeeded,
+    Failed(String),
+    /// Included in a block, execution result not reported yet.
+    Pending,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundDeploy {
+    pub hash: String,
+    pub block_height: u64,
+    pub outcome: DeployOutcome,
+}
+
+pub struct NodeClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl NodeClient {
+    /// `node_address` is the profile's, with or without the `/rpc` path.
+    pub fn new(node_address: &str) -> Self {
+        let base = node_address.trim_end_matches('/');
+        let url = if base.ends_with("/rpc") { base.to_string() } else { format!("{}/rpc", base) };
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .build();
+        Self { url, agent }
+    }
+
+    /// Height of the latest block.
+    pub fn block_height(&self) -> Result<u64, String> {
+        let result = self.call("chain_get_block", Value::Null)?;
+        block_height(&result).ok_or_else(|| String::from("chain_get_block: no block height"))
+    }
+
+    /// The deploy `sender` sent to `entry_point` in a block after
+    /// `after_height`. If it was sent more than once (a resubmission), a
+    /// succeeded one is preferred, otherwise the latest is returned.
+    pub fn find_deploy(&self, sender: &Address, entry_point: &str, after_height: u64) -> Result<Option<FoundDeploy>, String> {
+        let tip = self.block_height()?.min(after_height.saturating_add(MAX_BLOCKS_SCANNED));
+        let mut found: Option<FoundDeploy> = None;
+        for height in after_height.saturating_add(1)..=tip {
+            let block = self.call("chain_get_block", json!({ "block_identifier": { "Height": height } }))?;
+            for hash in block_deploy_hashes(&block) {
+                let deploy = self.call("info_get_deploy", json!({ "deploy_hash": hash }))?;
+                if !sent_by(&deploy, sender) || !calls_entry_point(&deploy, entry_point) {
+                    continue;
+                }
+                let outcome = execution_outcome(&deploy);
+                let succeeded = outcome == DeployOutcome::Succeeded;
+                found = Some(FoundDeploy { hash, block_height: height, outcome });
+                if succeeded {
+                    return Ok(found);
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let mut request = json!({ "jsonrpc": "2.0", "id": 1, "method": method });
+        if !params.is_null() {
+            request["params"] = params;
+        }
+        let response: Value = self.agent
+            .post(&self.url)
+            .send_json(request)
+            .map_err(|e| format!("{} {}: {}", method, self.url, e))?
+            .into_json()
+            .map_err(|e| format!("{} {}: {}", method, self.url, e))?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("{}: {}", method, error));
+        }
+        match response.get("result") {
+            Some(result) => Ok(result.clone()),
+            None => Err(format!("{}: no result", method)),
+        }
+    }
+}
+
+/// The block in a chain_get_block result, unwrapped from its version.
+fn block(result: &Value) -> &Value {
+    let block = match result.get("block_with_signatures") {
+        Some(signed) => &signed["block"],
+        None => &result["block"],
+    };
+    block.get("Version2").or_else(|| block.get("Version1")).unwrap_or(block)
+}
+
+fn block_height(result: &Value) -> Option<u64> {
+    block(result)["header"]["height"].as_u64()
+}
+
+/// Deploy hashes in a block: `deploy_hashes` on 1.x, `Deploy` entries of
+/// the per-lane `transactions` on 2.x.
+fn block_deploy_hashes(result: &Value) -> Vec<String> {
+    let body = &block(result)["body"];
+    if let Some(hashes) = body["deploy_hashes"].as_array() {
+        return hashes.iter().filter_map(|h| h.as_str()).map(String::from).collect();
+    }
+    body["transactions"].as_object()
+        .into_iter()
+        .flat_map(|lanes| lanes.values())
+        .filter_map(|lane| lane.as_array())
+        .flatten()
+        .filter_map(|transaction| transaction["Deploy"].as_str())
+        .map(String::from)
+        .collect()
+}
+
+fn sent_by(result: &Value, sender: &Address) -> bool {
+    result["deploy"]["header"]["account"].as_str()
+        .and_then(|key| PublicKey::from_hex(key).ok())
+        .is_some_and(|key| Address::from(key.to_account_hash()) == *sender)
+}
+
+/// Stored contract calls name the entry point directly; Odra's proxy
+/// caller (used for calls with attached CSPR) carries it as an argument.
+fn calls_entry_point(result: &Value, entry_point: &str) -> bool {
+    let Some(session) = result["deploy"]["session"].as_object().and_then(|s| s.values().next()) else {
+        return false;
+    };
+    if let Some(name) = session["entry_point"].as_str() {
+        return name == entry_point;
+    }
+    session["args"].as_array()
+        .into_iter()
+        .flatten()
+        .any(|arg| arg[0] == "entry_point" && arg[1]["parsed"] == entry_point)
+}
+
+fn execution_outcome(result: &Value) -> DeployOutcome {
+    // 2.x: execution_info.execution_result.{Version1|Version2}
+    let execution = &result["execution_info"]["execution_result"];
+    if let Some(v2) = execution.get("Version2") {
+        return match v2["error_message"].as_str() {
+            Some(message) => DeployOutcome::Failed(message.to_string()),
+            None => DeployOutcome::Succeeded,
+        };
+    }
+    // 1.x: execution_results[].result, also the shape of a 2.x Version1 result
+    let v1 = match execution.get("Version1") {
+        Some(v1) => v1,
+        None => &result["execution_results"][0]["result"],
+    };
+    if v1.get("Success").is_some() {
+        DeployOutcome::Succeeded
+    } else if let Some(failure) = v1.get("Failure") {
+        DeployOutcome::Failed(failure["error_message"].as_str().unwrap_or("execution failed").to_string())
+    } else {
+        DeployOutcome::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0106ca7c39cd272dbf21a86eeb3b36b7c26e2e9b94af64292419f7862936bca2ca";
+
+    fn sender() -> Address {
+        Address::from(PublicKey::from_hex(KEY).unwrap().to_account_hash())
+    }
+
+    #[test]
+    fn test_reads_blocks_of_both_versions() {
+        let v1 = json!({ "block": {
+            "header": { "height": 7 },
+            "body": { "deploy_hashes": ["aa"], "transfer_hashes": ["bb"] },
+        }});
+        assert_eq!(block_height(&v1), Some(7));
+        assert_eq!(block_deploy_hashes(&v1), ["aa"]);
+
+        let v2 = json!({ "block_with_signatures": { "block": { "Version2": {
+            "header": { "height": 9 },
+            "body": { "transactions": { "0": [{ "Deploy": "cc" }], "3": [{ "Version1": "dd" }, { "Deploy": "ee" }] } },
+        }}}});
+        assert_eq!(block_height(&v2), Some(9));
+        assert_eq!(block_deploy_hashes(&v2), ["cc", "ee"]);
+    }
+
+    #[test]
+    fn test_matches_own_deploys_and_reads_their_outcome() {
+        let stored = json!({
+            "deploy": {
+                "header": { "account": KEY },
+                "session": { "StoredVersionedContractByHash": { "entry_point": "admin_process_claim", "args": [] } },
+            },
+            "execution_info": { "execution_result": { "Version2": { "error_message": null } } },
+        });
+        assert!(sent_by(&stored, &sender()));
+        assert!(calls_entry_point(&stored, "admin_process_claim"));
+        assert!(!calls_entry_point(&stored, "admin_delegate"));
+        assert_eq!(execution_outcome(&stored), DeployOutcome::Succeeded);
+
+        let proxied = json!({
+            "deploy": {
+                "header": { "account": "01a62e8605be4c984ee547ac3da0cf3541561a92c5bb5de699aa4ec095b471bc81" },
+                "session": { "ModuleBytes": { "module_bytes": "", "args": [
+                    ["entry_point", { "cl_type": "String", "parsed": "admin_add_liquidity" }],
+                ] } },
+            },
+            "execution_results": [{ "result": { "Failure": { "error_message": "User error: 16" } } }],
+        });
+        assert!(!sent_by(&proxied, &sender()));
+        assert!(calls_entry_point(&proxied, "admin_add_liquidity"));
+        assert_eq!(execution_outcome(&proxied), DeployOutcome::Failed(String::from("User error: 16")));
+
+        let unexecuted = json!({ "deploy": {}, "execution_info": null });
+        assert_eq!(execution_outcome(&unexecuted), DeployOutcome::Pending);
+    }
+}
@@ -0,0 +1,226 @@
+//! Splits unstake demand (`pending_undelegations`) across validators.
+//!
+//! [`plan_undelegations`] takes from the validators that should shrink
+//! first: removed (inactive) validators, then active ones delegated above
+//! their target weight (an equal share of the pool's delegation, capped by
+//! `max_allocation`). Demand left after that is spread pro-rata over the
+//! remaining delegation, so weights stay where they are.
+//!
+//! A validator is never left with a delegation between zero and
+//! `min_remaining`. When partial undelegations cannot cover the demand
+//! under that rule, whole delegations are exited instead, which may
+//! undelegate slightly more than requested; the surplus lands in pool
+//! liquidity and is delegated again.
+
+use alloc::vec::Vec;
+use odra::casper_types::{PublicKey, U512};
+
+/// A validator's delegation as the planner sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorPosition {
+    pub validator: PublicKey,
+    pub delegated: U512,
+    /// Approved validators are active; removed ones keep their delegation
+    /// until it is moved out.
+    pub active: bool,
+    /// Per-validator cap from `ValidatorInfo` (zero = no cap).
+    pub max_allocation: U512,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Undelegation {
+    pub validator: PublicKey,
+    pub amount: U512,
+}
+
+/// Plans undelegations covering `demand`, at most one per validator, in
+/// `positions` order. Covers less only when the validators hold less.
+pub fn plan_undelegations(positions: &[ValidatorPosition], demand: U512, min_remaining: U512) -> Vec<Undelegation> {
+    let mut plan = Plan {
+        left: positions.iter().map(|p| p.delegated).collect(),
+        taken: positions.iter().map(|_| U512::zero()).collect(),
+        min_remaining,
+    };
+    let total: U512 = positions.iter().fold(U512::zero(), |sum, p| sum.saturating_add(p.delegated));
+    let mut unmet = demand.min(total);
+
+    // Inactive validators first, then active ones by excess over target
+    let targets = targets(positions, total);
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|&a, &b| {
+        let excess = |i: usize| positions[i].delegated.saturating_sub(targets[i]);
+        positions[a].active.cmp(&positions[b].active).then(excess(b).cmp(&excess(a)))
+    });
+    for &i in &order {
+        let excess = positions[i].delegated.saturating_sub(targets[i]);
+        unmet -= plan.take(i, excess.min(unmet));
+    }
+
+    // Pro-rata over what is left, the last validator takes the remainder
+    let spread: Vec<usize> = (0..positions.len()).filter(|&i| !plan.left[i].is_zero()).collect();
+    let spread_total = spread.iter().fold(U512::zero(), |sum, &i| sum + plan.left[i]);
+    let to_spread = unmet;
+    for (n, &i) in spread.iter().enumerate() {
+        if unmet.is_zero() {
+            break;
+        }
+        let share = if n + 1 == spread.len() {
+            unmet
+        } else {
+            mul_div(to_spread, plan.left[i], spread_total)
+        };
+        unmet -= plan.take(i, share.min(unmet));
+    }
+
+    // Partial takes wherever the minimum still leaves room, largest first
+    order.sort_by(|&a, &b| plan.left[b].cmp(&plan.left[a]));
+    for &i in &order {
+        unmet -= plan.take(i, unmet);
+    }
+
+    // Whole exits: the smallest delegation covering the rest, else the largest
+    while !unmet.is_zero() {
+        let covering = (0..positions.len())
+            .filter(|&i| plan.left[i] >= unmet)
+            .min_by_key(|&i| plan.left[i]);
+        let Some(i) = covering.or_else(|| (0..positions.len()).max_by_key(|&i| plan.left[i])) else {
+            break;
+        };
+        let amount = plan.left[i];
+        if amount.is_zero() {
+            break;
+        }
+        plan.left[i] = U512::zero();
+        plan.taken[i] += amount;
+        unmet = unmet.saturating_sub(amount);
+    }
+
+    positions
+        .iter()
+        .zip(plan.taken)
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(p, amount)| Undelegation { validator: p.validator.clone(), amount })
+        .collect()
+}
+
+struct Plan {
+    left: Vec<U512>,
+    taken: Vec<U512>,
+    min_remaining: U512,
+}
+
+impl Plan {
+    /// Takes up to `want` from validator `i`, shrinking it so the
+    /// remaining delegation is zero or at least `min_remaining`.
+    fn take(&mut self, i: usize, want: U512) -> U512 {
+        let left = self.left[i];
+        let mut amount = want.min(left);
+        let remaining = left - amount;
+        if !remaining.is_zero() && remaining < self.min_remaining {
+            amount = left.saturating_sub(self.min_remaining);
+        }
+        self.left[i] -= amount;
+        self.taken[i] += amount;
+        amount
+    }
+}
+
+/// Equal share of `total` per active validator, capped by its allocation.
+fn targets(positions: &[ValidatorPosition], total: U512) -> Vec<U512> {
+    let active = positions.iter().filter(|p| p.active).count();
+    let share = if active == 0 { U512::zero() } else { total / U512::from(active) };
+    positions
+        .iter()
+        .map(|p| match (p.active, p.max_allocation.is_zero()) {
+            (false, _) => U512::zero(),
+            (true, true) => share,
+            (true, false) => share.min(p.max_allocation),
+        })
+        .collect()
+}
+
+/// `a * b / c` without overflowing for amounts near U512::MAX.
+fn mul_div(a: U512, b: U512, c: U512) -> U512 {
+    if c.is_zero() {
+        return U512::zero();
+    }
+    match a.checked_mul(b) {
+        Some(product) => product / c,
+        None => a / c * b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSPR: u64 = 1_000_000_000;
+
+    fn validator(seed: u8) -> PublicKey {
+        let mut bytes = [0u8; 32];
+        bytes[0] = seed;
+        bytes[31] = seed;
+        PublicKey::ed25519_from_bytes(bytes).unwrap()
+    }
+
+    fn position(seed: u8, cspr: u64, active: bool) -> ValidatorPosition {
+        ValidatorPosition {
+            validator: validator(seed),
+            delegated: U512::from(cspr * CSPR),
+            active,
+            max_allocation: U512::zero(),
+        }
+    }
+
+    /// Plan as (seed, whole CSPR); tests number validators 1.. in order.
+    fn plan(positions: &[ValidatorPosition], demand: u64) -> Vec<(u8, u64)> {
+        plan_undelegations(positions, U512::from(demand * CSPR), U512::from(500 * CSPR))
+            .into_iter()
+            .map(|u| {
+                let index = positions.iter().position(|p| p.validator == u.validator).unwrap();
+                (index as u8 + 1, (u.amount / U512::from(CSPR)).as_u64())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_demand_prefers_inactive_then_over_weight() {
+        let positions = [position(1, 1_000, true), position(2, 3_000, true), position(3, 800, false)];
+        // Target is 2_400 each: 800 from the removed validator, then 600 over-weight
+        assert_eq!(plan(&positions, 1_000), [(2, 200), (3, 800)]);
+        assert_eq!(plan(&positions, 1_400), [(2, 600), (3, 800)]);
+
+        // A capped validator is over-weight above its cap
+        let mut capped = [position(1, 2_000, true), position(2, 2_000, true)];
+        capped[1].max_allocation = U512::from(1_500 * CSPR);
+        assert_eq!(plan(&capped, 500), [(2, 500)]);
+    }
+
+    #[test]
+    fn test_demand_larger_than_any_validator_is_split() {
+        let positions = [position(1, 2_000, true), position(2, 2_000, true), position(3, 2_000, true)];
+        assert_eq!(plan(&positions, 3_000), [(1, 1_000), (2, 1_000), (3, 1_000)]);
+
+        // Never more than is delegated
+        assert_eq!(plan(&positions, 10_000), [(1, 2_000), (2, 2_000), (3, 2_000)]);
+        assert!(plan(&positions, 0).is_empty());
+    }
+
+    #[test]
+    fn test_minimum_remaining_delegation_is_respected() {
+        // 1_000 split pro-rata would leave each validator 300 CSPR
+        let positions = [position(1, 800, true), position(2, 800, true)];
+        let planned = plan(&positions, 1_000);
+        let total: u64 = planned.iter().map(|(_, amount)| amount).sum();
+        assert!(total >= 1_000);
+        for (seed, amount) in &planned {
+            let left = 800 - amount;
+            assert!(left == 0 || left >= 500, "validator {} left with {} CSPR", seed, left);
+        }
+        assert_eq!(planned, [(1, 800), (2, 300)]);
+
+        // Exact motes when the minimum is not in the way
+        let exact = plan_undelegations(&[position(1, 2_000, true)], U512::from(1_234_567_891u64), U512::from(500 * CSPR));
+        assert_eq!(exact[0].amount, U512::from(1_234_567_891u64));
+    }
+}
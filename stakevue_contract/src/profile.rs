@@ -0,0 +1,290 @@
+//! Network profiles for the livenet binaries.
+//!
+//! Profiles live in `resources/profiles.toml`, one `[profiles.<name>]` table
+//! per network with its node, chain name, contract package hash, validator
+//! set and gas limits. A profile without `package_hash` falls back to the
+//! latest `StakeVue` entry in the Odra CLI's `resources/contracts.toml`
+//! next to it.
+//!
+//! ```toml
+//! [profiles.testnet]
+//! chain_name = "casper-test"
+//! node_address = "https://rpc.testnet.casperlabs.io/rpc"
+//! package_hash = "hash-..."
+//!
+//! [[profiles.testnet.validators]]
+//! name = "Make"
+//! public_key = "0106ca7c..."
+//!
+//! [profiles.testnet.gas]
+//! delegate = 50
+//!
+//! [profiles.testnet.alerts]
+//! max_consecutive_errors = 3
+//!
+//! [profiles.testnet.unbonding]
+//! period_secs = 50400
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::format;
+
+use odra::casper_types::{AsymmetricType, PublicKey};
+use odra::prelude::Address;
+use serde::Deserialize;
+
+use crate::alerts::AlertSettings;
+use crate::amounts::MOTES_PER_CSPR;
+use crate::unbonding::UnbondingSettings;
+
+/// Default location of the profiles file, relative to the crate root.
+pub const DEFAULT_PROFILES_PATH: &str = "resources/profiles.toml";
+
+/// [`DEFAULT_PROFILES_PATH`] under the crate root this was built from, so
+/// it resolves from any working directory.
+pub fn default_profiles_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_PROFILES_PATH)
+}
+
+/// Odra CLI deployment registry, looked up next to the profiles file.
+const DEPLOYED_CONTRACTS_FILE: &str = "contracts.toml";
+
+/// Contract name used in the deployment registry.
+const CONTRACT_NAME: &str = "StakeVue";
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, String),
+    UnknownProfile(String, Vec<String>),
+    MissingContract(String),
+    InvalidContract(String),
+    InvalidValidator(String),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Io(path, e) => write!(f, "Cannot read {}: {}", path.display(), e),
+            ProfileError::Parse(path, e) => write!(f, "Invalid {}: {}", path.display(), e),
+            ProfileError::UnknownProfile(name, known) => {
+                write!(f, "Unknown profile '{}' (available: {})", name, known.join(", "))
+            }
+            ProfileError::MissingContract(name) => write!(
+                f,
+                "Profile '{}' has no package_hash and no {} deployment is recorded",
+                name, CONTRACT_NAME
+            ),
+            ProfileError::InvalidContract(hash) => write!(f, "Invalid contract hash: {}", hash),
+            ProfileError::InvalidValidator(key) => write!(f, "Invalid validator key: {}", key),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidatorEntry {
+    #[serde(default)]
+    pub name: String,
+    pub public_key: String,
+}
+
+/// Gas limits per deploy type, in whole CSPR.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GasSettings {
+    pub deploy: u64,
+    pub stake: u64,
+    pub unstake: u64,
+    pub delegate: u64,
+    pub undelegate: u64,
+    pub admin: u64,
+}
+
+impl Default for GasSettings {
+    fn default() -> Self {
+        Self {
+            deploy: 600,
+            stake: 15,
+            unstake: 10,
+            delegate: 50,
+            undelegate: 50,
+            admin: 5,
+        }
+    }
+}
+
+impl GasSettings {
+    /// Converts a gas limit in CSPR to motes for `HostEnv::set_gas`,
+    /// clamping absurd limits instead of overflowing.
+    pub fn motes(cspr: u64) -> u64 {
+        cspr.saturating_mul(MOTES_PER_CSPR)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    pub chain_name: String,
+    pub node_address: String,
+    #[serde(default)]
+    pub events_url: Option<String>,
+    #[serde(default)]
+    pub package_hash: Option<String>,
+    #[serde(default)]
+    pub validators: Vec<ValidatorEntry>,
+    #[serde(default)]
+    pub gas: GasSettings,
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    #[serde(default)]
+    pub unbonding: UnbondingSettings,
+}
+
+#[derive(Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Deserialize)]
+struct DeployedContracts {
+    #[serde(default)]
+    contracts: Vec<DeployedContract>,
+}
+
+#[derive(Deserialize)]
+struct DeployedContract {
+    name: String,
+    package_hash: String,
+}
+
+impl Profile {
+    /// Loads profile `name` from `path`, resolving the package hash from
+    /// the deployment registry next to it when the profile has none.
+    pub fn load(path: &Path, name: &str) -> Result<Self, ProfileError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ProfileError::Io(path.to_path_buf(), e))?;
+        let mut profile = Self::from_toml(&content, name)
+            .map_err(|e| match e {
+                ProfileError::Parse(_, msg) => ProfileError::Parse(path.to_path_buf(), msg),
+                other => other,
+            })?;
+
+        if profile.package_hash.is_none() {
+            let registry = path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(DEPLOYED_CONTRACTS_FILE);
+            profile.package_hash = latest_deployment(&registry)?;
+        }
+        Ok(profile)
+    }
+
+    /// Parses profile `name` from a profiles file's contents.
+    pub fn from_toml(content: &str, name: &str) -> Result<Self, ProfileError> {
+        let file: ProfilesFile = toml::from_str(content)
+            .map_err(|e| ProfileError::Parse(PathBuf::new(), e.to_string()))?;
+        let known = file.profiles.keys().cloned().collect();
+        let mut profiles = file.profiles;
+        let mut profile = profiles
+            .remove(name)
+            .ok_or_else(|| ProfileError::UnknownProfile(name.to_string(), known))?;
+        profile.name = name.to_string();
+        Ok(profile)
+    }
+
+    /// Points the livenet environment at this profile's network.
+    ///
+    /// Must run before `odra_casper_livenet_env::env()`; values already set
+    /// by the profile win over `.env`, the secret key still comes from `.env`.
+    pub fn apply_env(&self) {
+        std::env::set_var("ODRA_CASPER_LIVENET_NODE_ADDRESS", &self.node_address);
+        std::env::set_var("ODRA_CASPER_LIVENET_CHAIN_NAME", &self.chain_name);
+        if let Some(events_url) = &self.events_url {
+            std::env::set_var("ODRA_CASPER_LIVENET_EVENT_ADDRESS", events_url);
+        }
+    }
+
+    pub fn contract_address(&self) -> Result<Address, ProfileError> {
+        let hash = self
+            .package_hash
+            .as_deref()
+            .ok_or_else(|| ProfileError::MissingContract(self.name.clone()))?;
+        Address::from_str(hash).map_err(|_| ProfileError::InvalidContract(hash.to_string()))
+    }
+
+    pub fn validator_keys(&self) -> Result<Vec<PublicKey>, ProfileError> {
+        self.validators
+            .iter()
+            .map(|v| {
+                PublicKey::from_hex(&v.public_key)
+                    .map_err(|_| ProfileError::InvalidValidator(v.public_key.clone()))
+            })
+            .collect()
+    }
+
+    /// Display name for a validator key, falling back to a short key prefix.
+    pub fn validator_name(&self, public_key: &str) -> String {
+        self.validators
+            .iter()
+            .find(|v| v.public_key.eq_ignore_ascii_case(public_key) && !v.name.is_empty())
+            .map(|v| v.name.clone())
+            .unwrap_or_else(|| format!("{}...", &public_key[..public_key.len().min(12)]))
+    }
+}
+
+fn latest_deployment(registry: &Path) -> Result<Option<String>, ProfileError> {
+    let content = match std::fs::read_to_string(registry) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(ProfileError::Io(registry.to_path_buf(), e)),
+    };
+    let deployed: DeployedContracts = toml::from_str(&content)
+        .map_err(|e| ProfileError::Parse(registry.to_path_buf(), e.to_string()))?;
+    Ok(deployed
+        .contracts
+        .into_iter()
+        .filter(|c| c.name == CONTRACT_NAME)
+        .last()
+        .map(|c| c.package_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_profiles_parse() {
+        let profile = Profile::load(&default_profiles_path(), "testnet").unwrap();
+        assert_eq!(profile.chain_name, "casper-test");
+        assert!(profile.contract_address().is_ok());
+        assert!(!profile.validator_keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_profile_defaults_and_unknown_name() {
+        let content = r#"
+            [profiles.local]
+            chain_name = "casper-net-1"
+            node_address = "http://localhost:11101/rpc"
+
+            [profiles.local.gas]
+            delegate = 20
+        "#;
+        let profile = Profile::from_toml(content, "local").unwrap();
+        assert_eq!(profile.gas.delegate, 20);
+        assert_eq!(profile.gas.admin, GasSettings::default().admin);
+        assert!(matches!(profile.contract_address(), Err(ProfileError::MissingContract(_))));
+
+        let err = Profile::from_toml(content, "mainnet").unwrap_err();
+        assert!(matches!(err, ProfileError::UnknownProfile(name, _) if name == "mainnet"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Rebasing stCSPR: the stCSPR share ledger exposed in CSPR units.
+//!
+//! `RebasingStCspr` is a separate contract in front of a StakeVue
+//! deployment. Balances are the CSPR value of an account's shares, so they
+//! grow with every harvest instead of the exchange rate. Transfers convert
+//! CSPR amounts to shares (rounding down) and move them with StakeVue's
+//! share allowances: `transfer` spends the holder's allowance to this
+//! contract, `transfer_from` the caller's allowance through
+//! `rebasing_view_transfer`, which only the view registered with
+//! `queue_set_rebasing_view` may call. The view keeps no balances or
+//! allowances of its own, so both views always describe the same ledger.
+
+use odra::casper_types::{U256, U512};
+use odra::prelude::*;
+
+use crate::{u256_to_u512, u512_to_u256, StakeVueContractRef};
+
+#[odra::event]
+pub struct RebasingTransfer {
+    pub from: Address,
+    pub to: Address,
+    pub cspr_amount: U512,
+    pub shares: U256,
+}
+
+#[odra::module(events = [RebasingTransfer])]
+pub struct RebasingStCspr {
+    stcspr: External<StakeVueContractRef>,
+}
+
+#[odra::module]
+impl RebasingStCspr {
+    /// Initialize over the StakeVue contract at `stcspr`
+    pub fn init(&mut self, stcspr: Address) {
+        self.stcspr.set(stcspr);
+    }
+
+    /// Get balance of an account in CSPR (grows with rewards)
+    pub fn balance_of(&self, account: Address) -> U512 {
+        self.get_cspr_by_shares(self.stcspr.get_stcspr_balance(account))
+    }
+
+    /// Get total supply in CSPR
+    pub fn total_supply(&self) -> U512 {
+        self.get_cspr_by_shares(self.stcspr.token_total_supply())
+    }
+
+    /// Transfer a CSPR-denominated amount of the caller's stCSPR
+    /// The caller approves this contract for the shares on StakeVue first
+    pub fn transfer(&mut self, recipient: Address, cspr_amount: U512) -> U256 {
+        let sender = self.env().caller();
+        let shares = self.get_shares_by_cspr(cspr_amount);
+        self.stcspr.transfer_from(sender, recipient, shares);
+        self.emit_transfer(sender, recipient, cspr_amount, shares)
+    }
+
+    /// Transfer a CSPR-denominated amount on behalf of `owner`
+    /// Allowances stay in shares, see StakeVue's allowance()
+    pub fn transfer_from(&mut self, owner: Address, recipient: Address, cspr_amount: U512) -> U256 {
+        let spender = self.env().caller();
+        let shares = self.get_shares_by_cspr(cspr_amount);
+        self.stcspr.rebasing_view_transfer(owner, spender, recipient, shares);
+        self.emit_transfer(owner, recipient, cspr_amount, shares)
+    }
+
+    /// Get stCSPR shares for a CSPR amount at the current rate
+    pub fn get_shares_by_cspr(&self, cspr_amount: U512) -> U256 {
+        let total_cspr = self.stcspr.get_total_pool();
+        let total_stcspr = self.stcspr.token_total_supply();
+
+        if total_stcspr == U256::zero() || total_cspr == U512::zero() {
+            return u512_to_u256(cspr_amount);
+        }
+
+        u512_to_u256((cspr_amount * u256_to_u512(total_stcspr)) / total_cspr)
+    }
+
+    /// Get CSPR value of a stCSPR share amount at the current rate
+    pub fn get_cspr_by_shares(&self, shares: U256) -> U512 {
+        let total_cspr = self.stcspr.get_total_pool();
+        let total_stcspr = self.stcspr.token_total_supply();
+
+        if total_stcspr == U256::zero() {
+            return U512::zero();
+        }
+
+        (u256_to_u512(shares) * total_cspr) / u256_to_u512(total_stcspr)
+    }
+}
+
+impl RebasingStCspr {
+    fn emit_transfer(&self, from: Address, recipient: Address, cspr_amount: U512, shares: U256) -> U256 {
+        self.env().emit_event(RebasingTransfer {
+            from,
+            to: recipient,
+            cspr_amount,
+            shares,
+        });
+        shares
+    }
+}
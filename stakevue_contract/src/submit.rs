@@ -0,0 +1,283 @@
+//! Deploy submission with confirmation and retries for the livenet binaries.
+//!
+//! Odra's livenet client blocks until a deploy has executed and returns its
+//! result, but panics on RPC and event stream failures (the "events 404"
+//! that `add_all_validators.sh` used to paper over by rerunning). A
+//! returned error is therefore a definitive execution result, while a panic
+//! leaves the outcome unknown: the deploy may or may not have been sent.
+//!
+//! [`submit`] treats an unknown outcome as "maybe applied". Before any
+//! resubmission it polls the action's `landed` check until
+//! [`RetryPolicy::confirm_timeout`] passes, so an action that went through
+//! is not sent a second time. Successful deploys are also confirmed through
+//! `landed`, so a call that executed without the expected effect is
+//! reported instead of printed as done.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::string::{String, ToString};
+use std::time::{Duration, Instant};
+use std::{fmt, format, thread};
+
+use odra::{OdraError, OdraResult};
+
+use crate::Error;
+
+macro_rules! stakevue_errors {
+    ($($name:ident),* $(,)?) => {
+        /// Name of the `StakeVue` error a failed call reverted with, if any.
+        pub fn error_name(err: &OdraError) -> Option<&'static str> {
+            $(
+                if *err == OdraError::from(Error::$name) {
+                    return Some(stringify!($name));
+                }
+            )*
+            None
+        }
+
+        #[cfg(test)]
+        const NAMED_ERRORS: usize = [$(stringify!($name)),*].len();
+    };
+}
+
+stakevue_errors! {
+    InsufficientBalance, InsufficientStCsprBalance, ZeroAmount,
+    InsufficientPoolBalance, NoValidatorSet, BelowMinimumDelegation,
+    ValidatorNotApproved, ValidatorAlreadyExists, WithdrawalNotReady,
+    WithdrawalNotFound, WithdrawalAlreadyClaimed, NotWithdrawalOwner,
+    MaxValidatorsReached, NoDelegationFound,
+    UndelegateAmountExceedsDelegation, InsufficientLiquidity,
+    NothingToDelegate, NothingToUndelegate, ContractPaused, RewardsTooHigh,
+    ValueOverflow, ValidatorNotFound, InvalidCommissionRate,
+    ValidatorCapExceeded, ConcentrationLimitExceeded, InvalidBasisPoints,
+    SameValidator, DowngradeNotAllowed, LegacyImportAlreadySet,
+    LegacyImportNotSet, InvalidMerkleProof, AlreadyMigrated,
+    LegacyReserveExceeded, EmergencyModeActive, EmergencyModeNotActive,
+    AdminStillActive, EmergencyAlreadyUndelegated, EmergencyNotUndelegated,
+    TimelockEtaTooSoon, OperationNotFound, OperationNotQueued,
+    OperationNotReady, OperationExpired, ApprovalRequired, NotApprover,
+    ProposalNotFound, ProposalAlreadyExecuted, AlreadyApproved,
+    ThresholdNotReached, InvalidThreshold, ApproverAlreadyExists,
+    ApproverNotFound, TvlCapExceeded, AccountStakeLimitExceeded,
+    BelowMinimumStake, NotAllowlisted, NotAllowlistManager, SelfReferral,
+    NoReferralRewards, PermitExpired, InvalidSignature, MultisigDisabled,
+    NoAccountSet, LegacyClaimWindowOpen, LegacyClaimWindowClosed,
+    ReferrerMismatch, InsufficientAllowance, NotRebasingView,
+    RebasingViewAlreadySet,
+}
+
+/// Human readable form of a failed call, naming `StakeVue` errors.
+pub fn describe_error(err: &OdraError) -> String {
+    match error_name(err) {
+        Some(name) => format!("reverted with {}", name),
+        None => format!("{:?}", err),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Submissions before giving up on transient failures.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// How long an unknown outcome is polled for before resubmitting.
+    pub confirm_timeout: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(60),
+            confirm_timeout: Duration::from_secs(180),
+            poll_interval: Duration::from_secs(15),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The contract rejected the call with a `StakeVue` error.
+    Reverted(&'static str),
+    /// The deploy executed and failed for another reason.
+    Failed(String),
+    /// The deploy executed but `landed` does not see its effect.
+    Unconfirmed,
+    /// Every attempt ended with an unknown outcome and nothing landed.
+    GaveUp { attempts: u32, last: String },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Reverted(name) => write!(f, "reverted with {}", name),
+            SubmitError::Failed(e) => write!(f, "failed: {}", e),
+            SubmitError::Unconfirmed => write!(f, "executed but the effect is not visible on chain"),
+            SubmitError::GaveUp { attempts, last } => {
+                write!(f, "gave up after {} attempt(s): {}", attempts, last)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Sends `send` until it executes, never resubmitting while a previous
+/// attempt might still land. `landed` must report whether the action's
+/// effect is visible in contract state. Returns the number of submissions.
+pub fn submit<S, L>(policy: &RetryPolicy, mut send: S, mut landed: L) -> Result<u32, SubmitError>
+where
+    S: FnMut() -> OdraResult<()>,
+    L: FnMut() -> bool,
+{
+    let mut backoff = policy.initial_backoff;
+    let mut last = String::new();
+
+    for attempt in 1..=policy.max_attempts.max(1) {
+        match panic::catch_unwind(AssertUnwindSafe(&mut send)) {
+            Ok(Ok(())) => {
+                return if check(&mut landed) == Some(true) {
+                    Ok(attempt)
+                } else {
+                    Err(SubmitError::Unconfirmed)
+                };
+            }
+            Ok(Err(err)) => {
+                return Err(match error_name(&err) {
+                    Some(name) => SubmitError::Reverted(name),
+                    None => SubmitError::Failed(format!("{:?}", err)),
+                });
+            }
+            Err(panic) => last = panic_message(panic),
+        }
+
+        // Unknown outcome: wait for the attempt to show up before resending
+        if confirm(policy, &mut landed) {
+            return Ok(attempt);
+        }
+        if attempt < policy.max_attempts {
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(policy.max_backoff);
+        }
+    }
+
+    Err(SubmitError::GaveUp { attempts: policy.max_attempts.max(1), last })
+}
+
+/// Runs `read`, turning a panic into an error. Livenet state queries panic
+/// on RPC failures just like submissions do; the bot runs each cycle
+/// through this so a flaky node fails the cycle instead of the process.
+pub fn guarded<T, R>(read: R) -> Result<T, String>
+where
+    R: FnOnce() -> Result<T, String>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(read)) {
+        Ok(result) => result,
+        Err(panic) => Err(format!("query failed: {}", panic_message(panic))),
+    }
+}
+
+/// Polls `landed` until it holds or the confirm timeout passes.
+fn confirm<L: FnMut() -> bool>(policy: &RetryPolicy, landed: &mut L) -> bool {
+    let deadline = Instant::now() + policy.confirm_timeout;
+    loop {
+        if check(landed) == Some(true) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        thread::sleep(policy.poll_interval);
+    }
+}
+
+/// Runs a state query, which can itself hit a flaky RPC node.
+fn check<L: FnMut() -> bool>(landed: &mut L) -> Option<bool> {
+    panic::catch_unwind(AssertUnwindSafe(landed)).ok()
+}
+
+fn panic_message(panic: std::boxed::Box<dyn std::any::Any + Send>) -> String {
+    if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = panic.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("panic during submission")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            confirm_timeout: Duration::ZERO,
+            poll_interval: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_error_names_cover_every_variant() {
+        assert_eq!(NAMED_ERRORS, Error::RebasingViewAlreadySet as usize);
+        let err: OdraError = Error::InsufficientLiquidity.into();
+        assert_eq!(error_name(&err), Some("InsufficientLiquidity"));
+        assert_eq!(describe_error(&err), "reverted with InsufficientLiquidity");
+    }
+
+    #[test]
+    fn test_submit_does_not_resend_an_attempt_that_landed() {
+        let sent = Cell::new(0);
+        let applied = Cell::new(false);
+        let result = submit(
+            &fast_policy(),
+            || {
+                sent.set(sent.get() + 1);
+                applied.set(true);
+                panic!("event stream returned 404");
+            },
+            || applied.get(),
+        );
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(sent.get(), 1);
+    }
+
+    #[test]
+    fn test_submit_retries_and_stops_on_revert() {
+        let sent = Cell::new(0);
+        let result = submit(
+            &fast_policy(),
+            || {
+                sent.set(sent.get() + 1);
+                if sent.get() < 2 {
+                    panic!("rpc timeout");
+                }
+                Err(Error::WithdrawalAlreadyClaimed.into())
+            },
+            || false,
+        );
+        assert!(matches!(result, Err(SubmitError::Reverted("WithdrawalAlreadyClaimed"))));
+        assert_eq!(sent.get(), 2);
+
+        let result = submit(&fast_policy(), || -> OdraResult<()> { panic!("rpc timeout") }, || false);
+        assert!(matches!(result, Err(SubmitError::GaveUp { attempts: 3, .. })));
+    }
+
+    #[test]
+    fn test_guarded_turns_a_failing_reader_into_an_error() {
+        let reader = |fail: bool| -> u64 {
+            if fail {
+                panic!("rpc returned 503");
+            }
+            7
+        };
+        assert_eq!(guarded(|| Ok(reader(false))), Ok(7));
+        assert_eq!(guarded(|| Ok(reader(true))), Err(String::from("query failed: rpc returned 503")));
+        assert_eq!(guarded(|| -> Result<u64, String> { Err(String::from("journal")) }), Err(String::from("journal")));
+    }
+}
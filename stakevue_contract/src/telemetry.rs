@@ -0,0 +1,206 @@
+//! Prometheus metrics, health endpoint and JSON logs for the bot.
+//!
+//! The HTTP side is a plain `TcpListener` on a background thread serving
+//! `GET /metrics` (Prometheus text format 0.0.4) and `GET /healthz`; the bot
+//! only needs two fixed routes, so no web framework is pulled in.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::string::{String, ToString};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{format, println, thread};
+
+use odra::casper_types::U512;
+
+use crate::amounts::format_units;
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorGauge {
+    pub name: String,
+    pub delegated: U512,
+}
+
+/// Everything `/metrics` reports. Updated by the bot after each cycle.
+#[derive(Debug, Default)]
+pub struct BotMetrics {
+    pub total_pool: U512,
+    pub available_liquidity: U512,
+    pub pending_undelegations: U512,
+    pub pending_withdrawals: U512,
+    /// Raw contract rate, 1_000_000_000 = 1.0.
+    pub exchange_rate: U512,
+    /// Undelegated by the bot, not yet back in pool liquidity.
+    pub unbonding: U512,
+    /// Keyed by validator public key (hex).
+    pub validators: BTreeMap<String, ValidatorGauge>,
+    /// Keyed by action kind (delegate, undelegate, add_liquidity, claim).
+    pub actions: BTreeMap<String, u64>,
+    pub errors: u64,
+    pub cycles: u64,
+    /// Unix seconds of the last cycle that finished without error.
+    pub last_success: Option<u64>,
+    /// Unix seconds when the bot started.
+    pub started: u64,
+}
+
+pub type SharedMetrics = Arc<Mutex<BotMetrics>>;
+
+impl BotMetrics {
+    pub fn new() -> Self {
+        Self { started: unix_now(), ..Default::default() }
+    }
+
+    pub fn shared() -> SharedMetrics {
+        Arc::new(Mutex::new(Self::new()))
+    }
+
+    pub fn record_action(&mut self, kind: &str) {
+        *self.actions.entry(kind.to_string()).or_insert(0) += 1;
+    }
+
+    /// Healthy while the last successful cycle (or start, before the first
+    /// one) is more recent than `max_age`.
+    pub fn is_healthy(&self, max_age: Duration) -> bool {
+        let reference = self.last_success.unwrap_or(self.started);
+        unix_now().saturating_sub(reference) <= max_age.as_secs()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge\n{} {}", name, help, name, name, value);
+        };
+        gauge("stakevue_total_pool_motes", "Total CSPR in the pool", motes(self.total_pool));
+        gauge("stakevue_available_liquidity_motes", "Undelegated pool liquidity", motes(self.available_liquidity));
+        gauge("stakevue_pending_undelegations_motes", "Unstake demand not yet undelegated", motes(self.pending_undelegations));
+        gauge("stakevue_pending_withdrawals_motes", "Withdrawal requests not yet claimed", motes(self.pending_withdrawals));
+        gauge("stakevue_exchange_rate", "CSPR per stCSPR", format_units(self.exchange_rate));
+        gauge("stakevue_bot_unbonding_motes", "CSPR undelegated by the bot and not yet added back as liquidity",
+            motes(self.unbonding));
+        gauge("stakevue_bot_last_success_timestamp_seconds", "Unix time of the last successful cycle",
+            self.last_success.unwrap_or(0).to_string());
+
+        let _ = writeln!(out, "# HELP stakevue_validator_delegated_motes CSPR delegated per validator");
+        let _ = writeln!(out, "# TYPE stakevue_validator_delegated_motes gauge");
+        for (key, v) in &self.validators {
+            let _ = writeln!(out, "stakevue_validator_delegated_motes{{validator=\"{}\",name=\"{}\"}} {}",
+                key, escape(&v.name), motes(v.delegated));
+        }
+
+        let _ = writeln!(out, "# HELP stakevue_bot_actions_total Deploys confirmed by the bot");
+        let _ = writeln!(out, "# TYPE stakevue_bot_actions_total counter");
+        for (kind, count) in &self.actions {
+            let _ = writeln!(out, "stakevue_bot_actions_total{{action=\"{}\"}} {}", kind, count);
+        }
+
+        let _ = writeln!(out, "# HELP stakevue_bot_errors_total Cycles that ended with an error");
+        let _ = writeln!(out, "# TYPE stakevue_bot_errors_total counter");
+        let _ = writeln!(out, "stakevue_bot_errors_total {}", self.errors);
+        let _ = writeln!(out, "# HELP stakevue_bot_cycles_total Cycles run");
+        let _ = writeln!(out, "# TYPE stakevue_bot_cycles_total counter");
+        let _ = writeln!(out, "stakevue_bot_cycles_total {}", self.cycles);
+        out
+    }
+}
+
+/// Serves `/metrics` and `/healthz` on `addr` from a background thread.
+/// Returns the bound address (useful with port 0).
+pub fn serve(addr: &str, metrics: SharedMetrics, max_age: Duration) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream, &metrics, max_age);
+        }
+    });
+    Ok(local)
+}
+
+fn respond(mut stream: TcpStream, metrics: &SharedMetrics, max_age: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+
+    let (status, content_type, body) = {
+        let metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+        match path {
+            "/metrics" => ("200 OK", "text/plain; version=0.0.4", metrics.render()),
+            "/healthz" if metrics.is_healthy(max_age) => ("200 OK", "text/plain", String::from("ok\n")),
+            "/healthz" => ("503 Service Unavailable", "text/plain", String::from("stale\n")),
+            _ => ("404 Not Found", "text/plain", String::from("not found\n")),
+        }
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    )
+}
+
+/// Prints one JSON log line: `{"ts":..,"level":..,"event":..,<fields>}`.
+pub fn log_json(level: &str, event: &str, fields: serde_json::Value) {
+    let mut line = serde_json::json!({ "ts": unix_now(), "level": level, "event": event });
+    if let (Some(line), serde_json::Value::Object(fields)) = (line.as_object_mut(), fields) {
+        line.extend(fields);
+    }
+    println!("{}", line);
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn motes(value: U512) -> String {
+    value.to_string()
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_metrics_and_health_endpoints() {
+        let metrics = BotMetrics::shared();
+        {
+            let mut m = metrics.lock().unwrap();
+            m.available_liquidity = U512::from(500_000_000_000u64);
+            m.exchange_rate = U512::from(1_050_000_000u64);
+            m.validators.insert(String::from("0106ca"), ValidatorGauge {
+                name: String::from("Make"),
+                delegated: U512::from(7u64),
+            });
+            m.record_action("delegate");
+            m.record_action("delegate");
+        }
+        let addr = serve("127.0.0.1:0", metrics.clone(), Duration::from_secs(60)).unwrap();
+
+        let body = get(addr, "/metrics");
+        assert!(body.starts_with("HTTP/1.1 200 OK"));
+        assert!(body.contains("stakevue_available_liquidity_motes 500000000000\n"));
+        assert!(body.contains("stakevue_exchange_rate 1.05\n"));
+        assert!(body.contains("stakevue_validator_delegated_motes{validator=\"0106ca\",name=\"Make\"} 7\n"));
+        assert!(body.contains("stakevue_bot_actions_total{action=\"delegate\"} 2\n"));
+
+        assert!(get(addr, "/healthz").starts_with("HTTP/1.1 200 OK"));
+        metrics.lock().unwrap().last_success = Some(unix_now() - 3600);
+        assert!(get(addr, "/healthz").starts_with("HTTP/1.1 503"));
+        assert!(get(addr, "/nope").starts_with("HTTP/1.1 404"));
+    }
+}
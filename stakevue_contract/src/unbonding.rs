@@ -0,0 +1,207 @@
+//! Tracks the bot's undelegations until the unbonded CSPR is back.
+//!
+//! `admin_undelegate` only starts unbonding: after the unbonding period the
+//! auction pays the CSPR out, and it has to be moved into
+//! `available_liquidity` before claims can use it. The auction pays the
+//! contract purse for contract delegations, which
+//! `admin_credit_purse_liquidity` credits in place, and the owner's account
+//! when the owner was the delegator, which `admin_add_liquidity` sends back.
+//!
+//! Neither balance says where a given payout came from, so the tracker keeps
+//! baselines taken when the first of the outstanding undelegations was
+//! submitted and attributes growth above them to undelegations in
+//! submission order. The purse is measured as its surplus over
+//! `available_liquidity` and the reserves, which stakes, delegations and
+//! claims leave unchanged; the owner's balance also moves with gas, hence
+//! [`UnbondingSettings::fee_allowance`].
+//!
+//! ```toml
+//! [profiles.testnet.unbonding]
+//! period_secs = 50400   # 7 eras
+//! fee_allowance = 50    # CSPR of owner gas tolerated between checks
+//! ```
+
+use std::string::String;
+use std::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::amounts::Motes;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UnbondingSettings {
+    /// Seconds after an undelegation before its CSPR is looked for.
+    pub period_secs: u64,
+    /// Owner balance drop from gas tolerated when looking there, in CSPR.
+    pub fee_allowance: u64,
+}
+
+impl Default for UnbondingSettings {
+    fn default() -> Self {
+        Self {
+            period_secs: 7 * 2 * 60 * 60,
+            fee_allowance: 50,
+        }
+    }
+}
+
+/// Where unbonded CSPR was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Purse,
+    Owner,
+}
+
+impl Source {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Source::Purse => "purse",
+            Source::Owner => "owner",
+        }
+    }
+}
+
+/// Balances the tracker compares against its baselines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Balances {
+    /// Contract purse balance above `available_liquidity` and the reserves
+    /// (`get_purse_surplus`).
+    pub purse_surplus: Motes,
+    pub owner: Motes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unbonding {
+    /// Journal entry of the undelegation.
+    pub journal_id: u64,
+    /// Validator public key (hex).
+    pub validator: String,
+    pub amount: Motes,
+    /// Unix seconds when the undelegation was confirmed.
+    pub undelegated_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnbondingTracker {
+    /// Outstanding undelegations, oldest first.
+    pub pending: Vec<Unbonding>,
+    purse_base: Motes,
+    owner_base: Motes,
+    /// Purse payouts credited to liquidity since the baselines were taken,
+    /// which took them out of the purse surplus.
+    purse_credited: Motes,
+}
+
+impl UnbondingTracker {
+    /// Total CSPR still unbonding or waiting to be finalized.
+    pub fn outstanding(&self) -> Motes {
+        self.pending.iter().fold(Motes::zero(), |sum, u| sum.saturating_add(u.amount))
+    }
+
+    /// Starts tracking a confirmed undelegation. `before` are the balances
+    /// read before it was submitted.
+    pub fn track(&mut self, unbonding: Unbonding, before: Balances) {
+        if self.pending.is_empty() {
+            self.purse_base = before.purse_surplus;
+            self.owner_base = before.owner;
+            self.purse_credited = Motes::zero();
+        }
+        self.pending.push(unbonding);
+    }
+
+    /// Undelegations whose CSPR has landed, oldest first, with where it was
+    /// found. Stops at the first one still unbonding or not found.
+    pub fn landed(&self, now: u64, settings: &UnbondingSettings, balances: Balances) -> Vec<(Unbonding, Source)> {
+        let mut purse_claimed = self.purse_base.saturating_add(self.purse_credited);
+        let mut owner_claimed = self.owner_base;
+        let purse_seen = balances.purse_surplus.saturating_add(self.purse_credited);
+        let owner_seen = balances.owner.saturating_add(Motes::from_cspr(settings.fee_allowance));
+
+        let mut landed = Vec::new();
+        for unbonding in &self.pending {
+            if now < unbonding.undelegated_at.saturating_add(settings.period_secs) {
+                break;
+            }
+            let purse_target = purse_claimed.saturating_add(unbonding.amount);
+            let owner_target = owner_claimed.saturating_add(unbonding.amount);
+            let source = if purse_seen >= purse_target {
+                purse_claimed = purse_target;
+                Source::Purse
+            } else if owner_seen >= owner_target {
+                owner_claimed = owner_target;
+                Source::Owner
+            } else {
+                break;
+            };
+            landed.push((unbonding.clone(), source));
+        }
+        landed
+    }
+
+    /// Stops tracking an undelegation once its CSPR is pool liquidity.
+    pub fn finalize(&mut self, journal_id: u64, source: Source) -> Option<Unbonding> {
+        let index = self.pending.iter().position(|u| u.journal_id == journal_id)?;
+        let unbonding = self.pending.remove(index);
+        match source {
+            // The payout was credited in place and left the surplus
+            Source::Purse => self.purse_credited = self.purse_credited.saturating_add(unbonding.amount),
+            // The payout went on from the owner into the contract
+            Source::Owner => {}
+        }
+        Some(unbonding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cspr(amount: u64) -> Motes {
+        Motes::from_cspr(amount)
+    }
+
+    fn unbonding(journal_id: u64, amount: u64, undelegated_at: u64) -> Unbonding {
+        Unbonding {
+            journal_id,
+            validator: String::from("0106ca"),
+            amount: cspr(amount),
+            undelegated_at,
+        }
+    }
+
+    fn balances(purse_surplus: u64, owner: u64) -> Balances {
+        Balances { purse_surplus: cspr(purse_surplus), owner: cspr(owner) }
+    }
+
+    #[test]
+    fn test_payouts_are_matched_in_order_after_the_period() {
+        let settings = UnbondingSettings { period_secs: 100, fee_allowance: 5 };
+        let mut tracker = UnbondingTracker::default();
+        tracker.track(unbonding(1, 600, 1_000), balances(10, 1_000));
+        tracker.track(unbonding(2, 400, 1_050), balances(10, 999));
+        assert_eq!(tracker.outstanding(), cspr(1_000));
+
+        // Still unbonding, then unbonded but not paid out yet
+        assert!(tracker.landed(1_050, &settings, balances(610, 998)).is_empty());
+        assert!(tracker.landed(1_100, &settings, balances(10, 998)).is_empty());
+
+        // Only the first payout reached the purse
+        let landed = tracker.landed(1_200, &settings, balances(610, 998));
+        assert_eq!(landed, [(unbonding(1, 600, 1_000), Source::Purse)]);
+
+        // Crediting it in place empties the surplus without costing the
+        // owner anything; the second payout then shows up in the owner's
+        // account despite some gas spent
+        assert_eq!(tracker.finalize(1, Source::Purse), Some(unbonding(1, 600, 1_000)));
+        assert!(tracker.landed(1_200, &settings, balances(10, 997)).is_empty());
+        let landed = tracker.landed(1_200, &settings, balances(10, 1_397));
+        assert_eq!(landed, [(unbonding(2, 400, 1_050), Source::Owner)]);
+
+        tracker.finalize(2, Source::Owner);
+        assert!(tracker.pending.is_empty());
+        assert!(tracker.finalize(2, Source::Owner).is_none());
+    }
+}